@@ -0,0 +1,70 @@
+use aoc::{
+    answers::Answers,
+    anyhow::{self, anyhow, Context},
+    registry::Day,
+    Challenge,
+};
+
+#[derive(Default)]
+struct Reader {
+    current_calories: usize,
+    elves: Vec<usize>,
+}
+
+impl Reader {
+    fn add_calories(&mut self, how_many: usize) {
+        self.current_calories += how_many;
+    }
+
+    fn flush(&mut self) {
+        if self.current_calories > 0 {
+            self.elves.push(self.current_calories);
+            self.current_calories = 0;
+        }
+    }
+}
+
+/// Returns the total calories carried by each elf.
+fn parse_elves(input: &str) -> anyhow::Result<Vec<usize>> {
+    let mut reader = Reader::default();
+    for line in input.lines() {
+        if line.is_empty() {
+            reader.flush();
+        } else {
+            let calories = line.parse::<usize>().context("parse number of calories")?;
+            reader.add_calories(calories);
+        }
+    }
+    reader.flush();
+    Ok(reader.elves)
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let mut elves = answers.time_parse(|| parse_elves(&challenge.input))?;
+
+    let part_1 = answers.time_part(1, || {
+        elves
+            .iter()
+            .copied()
+            .max()
+            .ok_or_else(|| anyhow!("no lines in input file?"))
+    })?;
+    answers.set(1, part_1);
+
+    let part_2 = answers.time_part(2, || {
+        elves.sort_by(|a, b| a.cmp(b).reverse());
+        elves.iter().take(3).sum::<usize>()
+    });
+    answers.set(2, part_2);
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 1,
+    solver: challenge_main,
+    debug_flags: &[],
+};
+
+aoc::example_test!(challenge_main, "example.txt", part1 = 24000, part2 = 45000);
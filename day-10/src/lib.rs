@@ -0,0 +1,181 @@
+use aoc::{
+    answers::{Answer, Answers},
+    anyhow::{self, anyhow, bail, Context},
+    bitmap::{Bitmap, Render},
+    debug::DebugFlag,
+    registry::Day,
+    Challenge,
+};
+
+aoc::debug_flags! {
+    enum Flag {
+        /// Print the machine state after each executed instruction
+        Instructions = "instructions",
+        /// Print the value of X during every cycle
+        FullHistory = "full-history",
+        /// Print the signal strengths that are summed up in part 1
+        SignalStrengths = "signal-strengths",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Noop,
+    AddX(i64),
+}
+
+struct Vm {
+    x: i64,
+    x_history: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ExecuteOptions {
+    debug_instructions: bool,
+}
+
+impl Vm {
+    fn new() -> Self {
+        Self {
+            x: 1,
+            x_history: vec![],
+        }
+    }
+
+    fn next_cycle(&mut self) {
+        self.x_history.push(self.x);
+    }
+
+    fn execute(&mut self, program: &[Instruction], options: &ExecuteOptions) {
+        for instruction in program {
+            match instruction {
+                Instruction::Noop => self.next_cycle(),
+                Instruction::AddX(x) => {
+                    self.next_cycle();
+                    self.next_cycle();
+                    self.x += *x;
+                }
+            }
+            if options.debug_instructions {
+                println!("{instruction:?}");
+                println!(
+                    " -> X:{} cycles:{} (history: {:?})",
+                    self.x,
+                    self.x_history.len(),
+                    self.x_history
+                );
+            }
+        }
+        self.next_cycle();
+    }
+
+    fn signal_strength(&self, cycle: usize) -> i64 {
+        cycle as i64 * self.x_history[cycle - 1]
+    }
+}
+
+fn render_image(width: usize, x_history: &[i64]) -> Vec<bool> {
+    let mut pixels = vec![];
+    for (cycle, &x) in x_history.iter().enumerate() {
+        let scanline_x = (cycle % width) as i64;
+        pixels.push(scanline_x == x - 1 || scanline_x == x || scanline_x == x + 1);
+    }
+    pixels
+}
+
+fn parse_program(input: &str) -> anyhow::Result<Vec<Instruction>> {
+    let mut program = vec![];
+    for line in input.lines() {
+        let mut words = line.split_whitespace();
+        let opcode = words
+            .next()
+            .ok_or_else(|| anyhow!("missing opcode: {line}"))?;
+        program.push(match opcode {
+            "noop" => Instruction::Noop,
+            "addx" => {
+                let x = words
+                    .next()
+                    .ok_or_else(|| anyhow!("missing operand for addx: {line}"))?;
+                let x = x.parse().context("invalid integer")?;
+                Instruction::AddX(x)
+            }
+            _ => bail!("invalid opcode: '{opcode}'"),
+        });
+    }
+    Ok(program)
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let program = answers.time_parse(|| parse_program(&challenge.input))?;
+
+    // Both parts read the history of X, so running the program counts towards part 1.
+    let mut vm = Vm::new();
+    answers.time_part(1, || {
+        vm.execute(
+            &program,
+            &ExecuteOptions {
+                debug_instructions: challenge.debug(Flag::Instructions),
+            },
+        )
+    });
+
+    if challenge.debug(Flag::FullHistory) {
+        println!("full history: {:?}", vm.x_history);
+    }
+
+    let sum_of_signal_strengths = answers.time_part(1, || {
+        (20..=220)
+            .step_by(40)
+            .map(|cycle| (cycle, vm.signal_strength(cycle)))
+            .inspect(|(cycle, signal_strength)| {
+                if challenge.debug(Flag::SignalStrengths) {
+                    println!("signal strength @ cycle {cycle}: {signal_strength}");
+                }
+            })
+            .map(|(_, signal_strength)| signal_strength)
+            .sum::<i64>()
+    });
+    answers.set(1, sum_of_signal_strengths);
+
+    let lines = answers.time_part(2, || {
+        let width = 40;
+        let mut image = render_image(width, &vm.x_history);
+        let height = image.len() / width;
+        image.truncate(width * height);
+        let image = Bitmap {
+            elements: image,
+            width: width as u32,
+            height: height as u32,
+            out_of_bounds: false,
+        };
+        Render::default()
+            .text(&image, |&lit| if lit { '#' } else { '.' })
+            .lines()
+            .map(String::from)
+            .collect()
+    });
+    answers.set(2, Answer::Grid(lines));
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 10,
+    solver: challenge_main,
+    debug_flags: Flag::ALL,
+};
+
+aoc::example_test!(
+    challenge_main,
+    "example.txt",
+    part1 = 13140,
+    part2 = concat!(
+        "##..##..##..##..##..##..##..##..##..##..\n",
+        "###...###...###...###...###...###...###.\n",
+        "####....####....####....####....####....\n",
+        "#####.....#####.....#####.....#####.....\n",
+        "######......######......######......####\n",
+        "#######.......#######.......#######.....",
+    ),
+);
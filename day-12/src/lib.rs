@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use aoc::{
+    answers::Answers,
+    anyhow::{self, anyhow},
+    astar::Observer,
+    bitmap::{Bitmap, BitmapParser},
+    bitmap_search::{BitmapSearch, Connectivity, GridTrace},
+    debug::DebugFlag,
+    registry::Day,
+    Challenge,
+};
+
+aoc::debug_flags! {
+    enum Flag {
+        /// Print the path found in part 1, along with the cells explored to find it
+        Path = "path",
+        /// Print where the path found in part 2 starts
+        Part2 = "part2",
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Elevation(u8);
+
+impl Elevation {
+    fn can_visit_from(self, other: Self) -> bool {
+        self.0 <= other.0 + 1
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    start: Option<(i32, i32)>,
+    goal: Option<(i32, i32)>,
+}
+
+impl BitmapParser for Parser {
+    type Element = Elevation;
+
+    fn parse_element(&mut self, (x, y): (u32, u32), c: char) -> Option<Self::Element> {
+        let c = match c {
+            'S' => {
+                self.start = Some((x as i32, y as i32));
+                'a'
+            }
+            'E' => {
+                self.goal = Some((x as i32, y as i32));
+                'z'
+            }
+            _ => c,
+        };
+        Some(Elevation(c as u8 - b'a'))
+    }
+}
+
+struct Hills {
+    start: (i32, i32),
+    goal: (i32, i32),
+    bitmap: Bitmap<Elevation>,
+}
+
+impl FromStr for Hills {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (bitmap, parser) = Bitmap::parse(Parser::default(), s)?;
+        Ok(Self {
+            start: parser
+                .start
+                .ok_or_else(|| anyhow!("heightmap is missing start point"))?,
+            goal: parser
+                .goal
+                .ok_or_else(|| anyhow!("heightmap is missing goal point"))?,
+            bitmap,
+        })
+    }
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let hills = answers.time_parse(|| challenge.input.parse::<Hills>())?;
+
+    // The search is reused by part 2, but building it counts towards part 1.
+    let mut search = answers.time_part(1, || {
+        BitmapSearch::new(&hills.bitmap, Connectivity::Four, |&from, &to| {
+            to.can_visit_from(from).then_some(1_u32)
+        })
+    });
+
+    let mut trace = GridTrace::default();
+    let observer: &mut dyn Observer<_, _> = if challenge.debug(Flag::Path) {
+        &mut trace
+    } else {
+        &mut ()
+    };
+    let part_1 = answers.time_part(1, || {
+        search.a_star_observed([hills.start], hills.goal, observer)
+    });
+    if let Some(path) = part_1 {
+        if challenge.debug(Flag::Path) {
+            print!(
+                "{}",
+                trace.render(&hills.bitmap, &path.nodes, |&elevation| {
+                    (b'a' + elevation.0) as char
+                })
+            );
+            println!("{} nodes expanded", path.expanded);
+        }
+        answers.set(1, path.cost);
+    }
+
+    let part_2 = answers
+        .time_part(2, || {
+            let lowest_points = hills
+                .bitmap
+                .positions()
+                .filter(|&(x, y)| hills.bitmap[(x, y)] == Elevation(0))
+                .collect::<Vec<_>>();
+            search.a_star(lowest_points, hills.goal)
+        })
+        .ok_or_else(|| anyhow!("no optimal path found"))?;
+    if challenge.debug(Flag::Part2) {
+        println!(
+            "path found from {:?} ({} nodes expanded)",
+            part_2.nodes[0], part_2.expanded
+        );
+    }
+    answers.set(2, part_2.cost);
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 12,
+    solver: challenge_main,
+    debug_flags: Flag::ALL,
+};
+
+aoc::example_test!(challenge_main, "example.txt", part1 = 31, part2 = 29);
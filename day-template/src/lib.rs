@@ -0,0 +1,13 @@
+use aoc::{answers::Answers, anyhow, registry::Day, Challenge};
+
+pub fn challenge_main(_challenge: Challenge) -> anyhow::Result<Answers> {
+    let answers = Answers::new();
+    Ok(answers)
+}
+
+// Fill in the day number and add the day to the runner's registry.
+pub const DAY: Day = Day {
+    number: 0,
+    solver: challenge_main,
+    debug_flags: &[],
+};
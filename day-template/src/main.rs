@@ -1,12 +1,3 @@
-use aoc::{
-    anyhow::{self, anyhow},
-    wrap_main, Challenge,
-};
-
-fn challenge_main(challenge: Challenge) -> anyhow::Result<()> {
-    Ok(())
-}
-
 fn main() {
-    wrap_main(challenge_main)
+    aoc::wrap_main(day_template::DAY)
 }
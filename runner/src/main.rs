@@ -0,0 +1,307 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use aoc::{
+    anyhow::{self, bail, Context},
+    init_logger,
+    log::{error, info},
+    log_summary,
+    owo_colors::OwoColorize,
+    registry::{Day, DaySelection, Registry},
+    report::{OutputFormat, Record},
+    run_challenges, write_report, ChallengeArgs, ChallengeResult,
+};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+
+#[derive(Parser)]
+struct RunnerArgs {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the selected days against their inputs.
+    Run {
+        /// Days to run: `7`, `1..=14`, `1..15`, or `all`.
+        days: DaySelection,
+        /// Directory containing a `day-N` directory of `.txt` input files for each day.
+        /// Ignored if inputs are passed explicitly.
+        #[clap(long, default_value = "inputs")]
+        inputs: PathBuf,
+        #[clap(flatten)]
+        challenge: ChallengeArgs,
+    },
+}
+
+fn registry() -> Registry {
+    let mut registry = Registry::default();
+    for day in [
+        day_1::DAY,
+        day_2::DAY,
+        day_3::DAY,
+        day_4::DAY,
+        day_5::DAY,
+        day_6::DAY,
+        day_7::DAY,
+        day_8::DAY,
+        day_9::DAY,
+        day_10::DAY,
+        day_11::DAY,
+        day_12::DAY,
+        day_14::DAY,
+    ] {
+        registry.register(day);
+    }
+    registry
+}
+
+fn debug_flags_help(registry: &Registry) -> String {
+    let mut help = String::from("Debug flags:");
+    for day in registry.days() {
+        if day.debug_flags.is_empty() {
+            continue;
+        }
+        let _ = write!(help, "\n  day {}:", day.number);
+        for flag in day.debug_flags {
+            let _ = write!(help, "\n    {flag}");
+        }
+    }
+    help
+}
+
+/// Makes sure each debug flag is understood by at least one of the selected days.
+fn check_debug_flags(days: &[&Day], debug_flags: &[String]) -> anyhow::Result<()> {
+    for name in debug_flags {
+        if !days
+            .iter()
+            .any(|day| day.debug_flags.iter().any(|flag| flag.name == name))
+        {
+            bail!("none of the selected days has a debug flag named {name:?}");
+        }
+    }
+    Ok(())
+}
+
+/// Explicit inputs (files, stdin, or `--input-str`) are not tied to a day, so they only make sense
+/// when a single day is selected. Otherwise every day would be run against the same input, and the
+/// first day would consume all of stdin, leaving nothing for the others.
+fn check_explicit_inputs(days: &[&Day], args: &ChallengeArgs) -> anyhow::Result<()> {
+    if days.len() > 1 {
+        if !args.input_files.is_empty() {
+            bail!("input files can only be passed when a single day is selected. use --inputs to run several days");
+        }
+        if !args.input_str.is_empty() {
+            bail!("--input-str can only be used when a single day is selected");
+        }
+    }
+    Ok(())
+}
+
+fn find_input_files(inputs: &Path, day: u32) -> anyhow::Result<Vec<PathBuf>> {
+    let directory = inputs.join(format!("day-{day}"));
+    if !directory.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut input_files = vec![];
+    for entry in std::fs::read_dir(&directory)
+        .with_context(|| format!("cannot read input directory {directory:?}"))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+            input_files.push(path);
+        }
+    }
+    input_files.sort();
+    Ok(input_files)
+}
+
+enum Outcome {
+    Ok(Vec<ChallengeResult>),
+    Failed(anyhow::Error),
+    NoInputs,
+}
+
+struct DayResult {
+    number: u32,
+    outcome: Outcome,
+}
+
+impl DayResult {
+    fn failed(&self) -> bool {
+        match &self.outcome {
+            Outcome::Ok(results) => results
+                .iter()
+                .any(|result| result.failed() || result.has_mismatches()),
+            Outcome::Failed(_) => true,
+            Outcome::NoInputs => false,
+        }
+    }
+}
+
+fn run_day(day: &Day, inputs: &Path, args: &ChallengeArgs) -> DayResult {
+    let mut args = args.clone();
+    args.debug
+        .retain(|name| day.debug_flags.iter().any(|flag| flag.name == name));
+    if args.input_files.is_empty() && args.input_str.is_empty() {
+        match find_input_files(inputs, day.number) {
+            Ok(input_files) => args.input_files = input_files,
+            Err(error) => {
+                error!("day {}: {error:?}", day.number);
+                return DayResult {
+                    number: day.number,
+                    outcome: Outcome::Failed(error),
+                };
+            }
+        }
+    }
+
+    let outcome = if args.input_files.is_empty() && args.input_str.is_empty() {
+        Outcome::NoInputs
+    } else {
+        info!("day {}", day.number);
+        Outcome::Ok(run_challenges(&args, day.solver))
+    };
+    DayResult {
+        number: day.number,
+        outcome,
+    }
+}
+
+fn print_table(results: &[DayResult]) {
+    let mut rows = vec![];
+    for result in results {
+        let day = result.number.to_string();
+        let mut row = |input: String, part_1, part_2, time, status: String| {
+            rows.push(([day.clone(), input, part_1, part_2, time], status));
+        };
+        match &result.outcome {
+            Outcome::Ok(results) => {
+                for result in results {
+                    let input = match result.source.path().and_then(Path::file_name) {
+                        Some(file_name) => file_name.to_string_lossy().into_owned(),
+                        None => result.source.to_string(),
+                    };
+                    let part = |part| {
+                        result
+                            .answers
+                            .as_ref()
+                            .ok()
+                            .and_then(|answers| answers.get(part))
+                            .map(|answer| answer.summary())
+                            .unwrap_or_default()
+                    };
+                    let time = format!("{:.2?}", result.timings.total);
+                    let status = if result.failed() {
+                        "failed".red().to_string()
+                    } else if result.has_mismatches() {
+                        "mismatch".red().to_string()
+                    } else {
+                        "ok".green().to_string()
+                    };
+                    row(input, part(1), part(2), time, status);
+                }
+            }
+            Outcome::Failed(_) => row(
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                "failed".red().to_string(),
+            ),
+            Outcome::NoInputs => row(
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                "no inputs".yellow().to_string(),
+            ),
+        }
+    }
+
+    let header = ["day", "input", "part 1", "part 2", "time"].map(String::from);
+    let mut widths = header.clone().map(|cell| cell.chars().count());
+    for (row, _) in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    println!();
+    let [day, input, part_1, part_2, time] = widths;
+    let header = (header, "result".to_owned());
+    for (row, status) in std::iter::once(&header).chain(&rows) {
+        println!(
+            "{:>day$}  {:<input$}  {:>part_1$}  {:>part_2$}  {:>time$}  {status}",
+            row[0], row[1], row[2], row[3], row[4],
+        );
+    }
+}
+
+fn records(results: &[DayResult]) -> Vec<Record> {
+    let mut records = vec![];
+    for result in results {
+        let day = Some(result.number);
+        match &result.outcome {
+            Outcome::Ok(results) => records.extend(
+                results
+                    .iter()
+                    .map(|result| Record::from_result(day, result)),
+            ),
+            Outcome::Failed(error) => records.push(Record::from_error(day, error)),
+            Outcome::NoInputs => (),
+        }
+    }
+    records
+}
+
+fn main() {
+    init_logger();
+
+    let registry = registry();
+    let matches = RunnerArgs::command()
+        .mut_subcommand("run", |run| run.after_help(debug_flags_help(&registry)))
+        .get_matches();
+    let args = RunnerArgs::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    match args.command {
+        Command::Run {
+            days,
+            inputs,
+            challenge,
+        } => {
+            let days = match registry
+                .select(&days)
+                .and_then(|days| check_debug_flags(&days, &challenge.debug).map(|()| days))
+                .and_then(|days| check_explicit_inputs(&days, &challenge).map(|()| days))
+            {
+                Ok(days) => days,
+                Err(error) => {
+                    error!("{error:?}");
+                    std::process::exit(1);
+                }
+            };
+
+            let results: Vec<_> = days
+                .into_iter()
+                .map(|day| run_day(day, &inputs, &challenge))
+                .collect();
+            if challenge.output == OutputFormat::Human {
+                print_table(&results);
+            } else {
+                write_report(challenge.output, &records(&results));
+            }
+            if challenge.keep_going {
+                log_summary(results.iter().flat_map(|result| match &result.outcome {
+                    Outcome::Ok(results) => results.as_slice(),
+                    _ => &[],
+                }));
+            }
+
+            if results.iter().any(|result| result.failed()) {
+                std::process::exit(1);
+            }
+        }
+    }
+}
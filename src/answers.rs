@@ -0,0 +1,451 @@
+//! Structured answers returned by solvers.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context};
+use owo_colors::OwoColorize;
+
+/// The answer to a single part of a challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    String(String),
+    /// A multi-line answer, like an image that has to be read by a human.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Shortened representation of the answer that fits on a single line.
+    pub fn summary(&self) -> String {
+        match self {
+            Answer::Grid(lines) => {
+                let width = lines.first().map(|line| line.chars().count()).unwrap_or(0);
+                format!("<{width}x{} grid>", lines.len())
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    /// The separator to put between `part N:` and the answer when displaying it.
+    fn separator(&self) -> &'static str {
+        match self {
+            Answer::Grid(_) => "\n",
+            _ => " ",
+        }
+    }
+
+    /// Whether the two answers would be printed the same way. Answers read back from text files
+    /// cannot always be told apart by type (eg. a string answer consisting of digits only,) hence
+    /// this is used for comparisons instead of `==`.
+    pub fn matches(&self, other: &Answer) -> bool {
+        self.to_string() == other.to_string()
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(x) => write!(f, "{x}"),
+            Answer::String(s) => write!(f, "{s}"),
+            Answer::Grid(lines) => {
+                for (i, line) in lines.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{line}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! impl_from_lossless_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    Answer::Integer(i64::from(x))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_lossless_integer!(i8, i16, i32, i64, u8, u16, u32);
+
+/// Integers that do not fit in an `i64` become string answers. They are displayed the same way
+/// either way, and [`FromStr`] reads them back as strings too.
+macro_rules! impl_from_wide_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    i64::try_from(x)
+                        .map(Answer::Integer)
+                        .unwrap_or_else(|_| Answer::String(x.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_wide_integer!(u64, usize, isize);
+
+impl FromStr for Answer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<i64>() {
+            Ok(x) => Answer::Integer(x),
+            Err(_) => Answer::String(s.to_owned()),
+        })
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_owned())
+    }
+}
+
+/// Answers to all parts of a challenge, keyed by part number.
+///
+/// Also keeps track of how long it took to arrive at each answer. Solvers wrap their parsing in
+/// [`time_parse`][Answers::time_parse] and the work for each part in
+/// [`time_part`][Answers::time_part]; anything outside of those, like debug output, is not timed.
+#[derive(Debug, Clone)]
+pub struct Answers {
+    parts: BTreeMap<u32, Answer>,
+    parse_time: Option<Duration>,
+    part_times: BTreeMap<u32, Duration>,
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Self {
+            parts: BTreeMap::new(),
+            parse_time: None,
+            part_times: BTreeMap::new(),
+        }
+    }
+
+    /// Runs `f`, counting the time it takes as parsing. Can be called more than once, in which case
+    /// the times add up.
+    pub fn time_parse<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        *self.parse_time.get_or_insert(Duration::ZERO) += start.elapsed();
+        result
+    }
+
+    /// Runs `f`, counting the time it takes towards the given part. Can be called more than once,
+    /// in which case the times add up.
+    pub fn time_part<R>(&mut self, part: u32, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        *self.part_times.entry(part).or_default() += start.elapsed();
+        result
+    }
+
+    pub fn set(&mut self, part: u32, answer: impl Into<Answer>) {
+        self.parts.insert(part, answer.into());
+    }
+
+    /// How long parsing took, if it was timed with [`time_parse`][Answers::time_parse].
+    pub fn parse_time(&self) -> Option<Duration> {
+        self.parse_time
+    }
+
+    pub fn part_times(&self) -> &BTreeMap<u32, Duration> {
+        &self.part_times
+    }
+
+    pub fn get(&self, part: u32) -> Option<&Answer> {
+        self.parts.get(&part)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &Answer)> {
+        self.parts.iter().map(|(&part, answer)| (part, answer))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Path of the file holding the expected answers for the given input file.
+    pub fn expected_answers_path(input_file: &Path) -> PathBuf {
+        let mut path = input_file.as_os_str().to_owned();
+        path.push(".answers");
+        PathBuf::from(path)
+    }
+
+    /// Loads the expected answers for the given input file. Returns `None` if the input file has
+    /// no answers file next to it.
+    pub fn load_expected(input_file: &Path) -> anyhow::Result<Option<Answers>> {
+        let path = Self::expected_answers_path(input_file);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let answers = std::fs::read_to_string(&path)
+            .context("read answers file")?
+            .replace("\r\n", "\n")
+            .parse()
+            .with_context(|| format!("cannot parse answers file {path:?}"))?;
+        Ok(Some(answers))
+    }
+
+    /// Compares these answers against the expected ones. Parts that do not have an expected
+    /// answer are not checked.
+    pub fn check(&self, expected: &Answers) -> Vec<Mismatch> {
+        expected
+            .iter()
+            .filter_map(|(part, expected)| {
+                let actual = self.get(part);
+                if actual.is_some_and(|actual| actual.matches(expected)) {
+                    None
+                } else {
+                    Some(Mismatch {
+                        part,
+                        expected: expected.clone(),
+                        actual: actual.cloned(),
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the answers like [`Display`][fmt::Display] does, but with answers matching the
+    /// expected ones colored green, and mismatches colored red.
+    pub fn display_checked(&self, expected: &Answers) -> String {
+        let mut parts: Vec<_> = self.parts.keys().chain(expected.parts.keys()).collect();
+        parts.sort();
+        parts.dedup();
+
+        let mut out = String::new();
+        for &part in parts {
+            let actual = self.get(part);
+            let separator = actual.map(Answer::separator).unwrap_or(" ");
+            let actual_text = actual
+                .map(|answer| answer.to_string())
+                .unwrap_or_else(|| "(missing)".to_owned());
+            let _ = match expected.get(part) {
+                None => writeln!(out, "part {part}:{separator}{actual_text}"),
+                Some(expected) if actual.is_some_and(|actual| actual.matches(expected)) => {
+                    writeln!(out, "part {part}:{separator}{}", actual_text.green())
+                }
+                Some(expected) => writeln!(
+                    out,
+                    "part {part}:{separator}{}\n  expected:{}{}",
+                    actual_text.red(),
+                    expected.separator(),
+                    expected.yellow()
+                ),
+            };
+        }
+        out
+    }
+}
+
+impl Default for Answers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Answers are compared by their values only; timings are not taken into account.
+impl PartialEq for Answers {
+    fn eq(&self, other: &Self) -> bool {
+        self.parts == other.parts
+    }
+}
+
+impl Eq for Answers {}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    /// Parses answers in the same format they're displayed in: `part N: answer` lines, or a
+    /// `part N:` line followed by the lines of a grid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn finish_grid(answers: &mut Answers, part: u32, mut lines: Vec<String>) {
+            while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                lines.pop();
+            }
+            answers.set(part, Answer::Grid(lines));
+        }
+
+        let mut answers = Answers::new();
+        let mut grid: Option<(u32, Vec<String>)> = None;
+        for line in s.lines() {
+            if let Some(header) = line.strip_prefix("part ") {
+                let Some((part, answer)) = header.split_once(':') else {
+                    bail!("answer line is missing ':' after the part number: {line}")
+                };
+                let part = part.trim().parse::<u32>().context("invalid part number")?;
+                if let Some((part, lines)) = grid.take() {
+                    finish_grid(&mut answers, part, lines);
+                }
+                let answer = answer.trim();
+                if answer.is_empty() {
+                    grid = Some((part, vec![]));
+                } else {
+                    answers.set(part, answer.parse::<Answer>()?);
+                }
+            } else if let Some((_, lines)) = &mut grid {
+                lines.push(line.to_owned());
+            } else if !line.trim().is_empty() {
+                bail!("line does not belong to any part's answer: {line}");
+            }
+        }
+        if let Some((part, lines)) = grid {
+            finish_grid(&mut answers, part, lines);
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (part, answer) in self.iter() {
+            writeln!(f, "part {part}:{}{answer}", answer.separator())?;
+        }
+        Ok(())
+    }
+}
+
+/// An answer that did not match the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: u32,
+    pub expected: Answer,
+    /// `None` if the solver did not produce an answer for this part at all.
+    pub actual: Option<Answer>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_integers_do_not_panic() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from(isize::MIN).to_string(), isize::MIN.to_string());
+        // Integers that fit stay integers.
+        assert_eq!(Answer::from(42_u64), Answer::Integer(42));
+    }
+
+    #[test]
+    fn wide_integers_round_trip_through_check() {
+        let mut answers = Answers::new();
+        answers.set(1, u64::MAX);
+        answers.set(2, 1_u64 << 63);
+
+        let expected: Answers = answers.to_string().parse().unwrap();
+        assert_eq!(expected.get(1).unwrap().to_string(), u64::MAX.to_string());
+        assert_eq!(answers.check(&expected), []);
+    }
+
+    fn grid(lines: &[&str]) -> Answer {
+        Answer::Grid(lines.iter().map(|&line| line.to_owned()).collect())
+    }
+
+    #[test]
+    fn parses_single_line_answers() {
+        let answers: Answers = "part 1: 13140\npart 2: CZGSH\n".parse().unwrap();
+        assert_eq!(answers.get(1), Some(&Answer::Integer(13140)));
+        assert_eq!(answers.get(2), Some(&Answer::String("CZGSH".to_owned())));
+        assert_eq!(answers.get(3), None);
+    }
+
+    #[test]
+    fn parses_grid_answers() {
+        let text = concat!(
+            "part 1: 13140\n",
+            "part 2:\n",
+            "##..##..##\n",
+            "###...###.\n",
+            "####....##\n",
+        );
+        let answers: Answers = text.parse().unwrap();
+        assert_eq!(answers.get(1), Some(&Answer::Integer(13140)));
+        assert_eq!(
+            answers.get(2),
+            Some(&grid(&["##..##..##", "###...###.", "####....##"]))
+        );
+        // Grids are written back out the same way they are read.
+        assert_eq!(answers.to_string(), text);
+    }
+
+    #[test]
+    fn grid_followed_by_another_part() {
+        let answers: Answers = "part 1:\n#.\n.#\n\npart 2: 7\n".parse().unwrap();
+        assert_eq!(answers.get(1), Some(&grid(&["#.", ".#"])));
+        assert_eq!(answers.get(2), Some(&Answer::Integer(7)));
+    }
+
+    #[test]
+    fn ignores_trailing_blank_lines() {
+        let answers: Answers = "part 1: 24000\n\n\n".parse().unwrap();
+        assert_eq!(answers.get(1), Some(&Answer::Integer(24000)));
+
+        let answers: Answers = "part 2:\n#.\n.#\n\n  \n".parse().unwrap();
+        assert_eq!(answers.get(2), Some(&grid(&["#.", ".#"])));
+    }
+
+    #[test]
+    fn rejects_missing_colon() {
+        let error = "part 1 24000\n".parse::<Answers>().unwrap_err();
+        assert!(error.to_string().contains("missing ':'"), "{error}");
+    }
+
+    #[test]
+    fn rejects_stray_lines() {
+        let error = "24000\npart 2: 45000\n".parse::<Answers>().unwrap_err();
+        assert!(error.to_string().contains("does not belong"), "{error}");
+    }
+
+    #[test]
+    fn matches_across_types() {
+        assert!(Answer::String("123".to_owned()).matches(&Answer::Integer(123)));
+        assert!(Answer::Integer(123).matches(&Answer::String("123".to_owned())));
+        assert!(grid(&["abc"]).matches(&Answer::String("abc".to_owned())));
+        assert!(!Answer::Integer(123).matches(&Answer::String("0123".to_owned())));
+    }
+
+    #[test]
+    fn check_reports_mismatches() {
+        let mut answers = Answers::new();
+        answers.set(1, "123");
+        answers.set(2, 5);
+        let expected: Answers = "part 1: 123\npart 2: 6\npart 3: 7\n".parse().unwrap();
+        assert_eq!(
+            answers.check(&expected),
+            [
+                Mismatch {
+                    part: 2,
+                    expected: Answer::Integer(6),
+                    actual: Some(Answer::Integer(5)),
+                },
+                Mismatch {
+                    part: 3,
+                    expected: Answer::Integer(7),
+                    actual: None,
+                },
+            ]
+        );
+    }
+}
@@ -1,63 +1,720 @@
 //! The A* pathfinding algorithm.
 
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
     fmt::Debug,
     hash::Hash,
+    ops::Add,
 };
 
-pub struct AStar<'a, Node> {
-    pub start: Node,
-    pub goal: Node,
-    pub heuristic: &'a dyn Fn(&Node) -> f32,
+use crate::graph::{from_visit, Graph};
+
+/// The type of edge weights and path costs.
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+
+    /// The cost of taking the given number of steps that cost 1 each, used by heuristics that
+    /// count steps. Types too small to represent it saturate, which keeps such heuristics from
+    /// overestimating.
+    fn from_steps(steps: u32) -> Self;
+}
+
+macro_rules! impl_cost_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+
+                fn from_steps(steps: u32) -> Self {
+                    Self::try_from(steps).unwrap_or(Self::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_cost_for_integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+/// An `f32` cost, ordered using [`f32::total_cmp`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TotalF32(pub f32);
+
+impl PartialEq for TotalF32 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalF32 {}
+
+impl PartialOrd for TotalF32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalF32 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for TotalF32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Cost for TotalF32 {
+    const ZERO: Self = Self(0.0);
+
+    fn from_steps(steps: u32) -> Self {
+        Self(steps as f32)
+    }
+}
+
+/// The node or nodes a search is looking for a path to.
+pub enum Goal<'a, Node> {
+    Node(Node),
+    /// Any of the nodes in the set. The heuristic must not overestimate the cost to the closest
+    /// one.
+    Set(HashSet<Node>),
+    /// Any node for which the predicate returns `true`.
+    Predicate(&'a dyn Fn(&Node) -> bool),
+}
+
+impl<'a, Node> Goal<'a, Node>
+where
+    Node: Eq + Hash,
+{
+    pub fn is_reached(&self, node: &Node) -> bool {
+        match self {
+            Goal::Node(goal) => goal == node,
+            Goal::Set(goals) => goals.contains(node),
+            Goal::Predicate(predicate) => predicate(node),
+        }
+    }
+}
+
+/// Notified of the steps a search takes, for debugging and visualizing it. All methods do
+/// nothing by default.
+pub trait Observer<Node, C> {
+    /// The node was added to the open set with the given g-score.
+    fn open(&mut self, _node: &Node, _g_score: C) {}
+
+    /// The node was taken out of the open set, and its neighbors are about to be visited.
+    fn expand(&mut self, _node: &Node) {}
+
+    /// A cheaper path to `to` was found, going through `from`.
+    fn relax(&mut self, _from: &Node, _to: &Node, _g_score: C) {}
+}
+
+/// Observes nothing.
+impl<Node, C> Observer<Node, C> for () {}
+
+/// Finds the shortest path from any of the start nodes to the goal, in a graph visited through a
+/// callback. See [`Searcher`] for searching any [`Graph`].
+pub struct AStar<'a, Node, C> {
+    pub starts: Vec<Node>,
+    pub goal: Goal<'a, Node>,
+    pub heuristic: &'a dyn Fn(&Node) -> C,
     #[allow(clippy::type_complexity)]
-    pub visit_neighbors: &'a dyn Fn(&Node, &mut dyn FnMut(&Node, f32)),
+    pub visit_neighbors: &'a dyn Fn(&Node, &mut dyn FnMut(&Node, C)),
+    pub observer: Option<&'a mut dyn Observer<Node, C>>,
 }
 
-impl<'a, Node> AStar<'a, Node>
+/// A path found by [`AStar`] or one of the searches in [`graph`][crate::graph].
+#[derive(Debug, Clone)]
+pub struct Path<Node, C> {
+    /// The nodes along the path, including the start and the goal that were connected.
+    pub nodes: Vec<Node>,
+    /// The sum of the weights of the edges along the path.
+    pub cost: C,
+    /// How many nodes were expanded (had their neighbors visited) during the search.
+    pub expanded: usize,
+}
+
+/// An entry in the open set. Entries are ordered such that the one with the lowest f-score is
+/// the greatest, as [`BinaryHeap`] is a max-heap.
+pub(crate) struct Open<Node, C> {
+    pub(crate) f_score: C,
+    pub(crate) g_score: C,
+    pub(crate) node: Node,
+}
+
+impl<Node, C: Ord> PartialEq for Open<Node, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Node, C: Ord> Eq for Open<Node, C> {}
+
+impl<Node, C: Ord> PartialOrd for Open<Node, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Node, C: Ord> Ord for Open<Node, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f_score.cmp(&self.f_score)
+    }
+}
+
+/// Follows `came_from` back from `current` to the node the search started at.
+pub(crate) fn reconstruct_path<Node>(
+    came_from: &HashMap<Node, Node>,
+    mut current: Node,
+) -> Vec<Node>
 where
-    Node: Debug + Clone + Eq + Hash + Ord,
+    Node: Clone + Eq + Hash,
 {
-    fn reconstruct_path(came_from: &HashMap<Node, Node>, mut current: Node) -> Vec<Node> {
-        let mut total_path = vec![];
-        while came_from.contains_key(&current) {
-            current = came_from[&current].clone();
-            total_path.push(current.clone());
-        }
-        total_path.reverse();
-        total_path
-    }
-
-    pub fn find_path(self) -> Option<Vec<Node>> {
-        let mut open_set = HashSet::new();
-        open_set.insert(self.start.clone());
-        let mut came_from = HashMap::new();
-        let mut g_score = HashMap::new();
-        g_score.insert(self.start.clone(), 0.0);
-        let mut f_score = HashMap::new();
-        f_score.insert(self.start.clone(), (self.heuristic)(&self.start));
-
-        while !open_set.is_empty() {
-            let current = open_set.iter().min().expect("no nodes in open_set").clone();
-            if current == self.goal {
-                return Some(Self::reconstruct_path(&came_from, current));
-            }
-
-            open_set.remove(&current);
-            (self.visit_neighbors)(&current, &mut |neighbor, weight| {
-                let tentative_g_score = g_score[&current] + weight;
-                if tentative_g_score < g_score.get(neighbor).copied().unwrap_or(f32::INFINITY) {
+    let mut total_path = vec![current.clone()];
+    while let Some(previous) = came_from.get(&current) {
+        current = previous.clone();
+        total_path.push(current.clone());
+    }
+    total_path.reverse();
+    total_path
+}
+
+impl<'a, Node, C> AStar<'a, Node, C>
+where
+    Node: Debug + Clone + Eq + Hash,
+    C: Cost,
+{
+    pub fn find_path(self) -> Option<Path<Node, C>> {
+        let mut searcher = Searcher::new(from_visit(self.visit_neighbors), self.heuristic);
+        match self.observer {
+            Some(observer) => searcher.find_path_observed(self.starts, &self.goal, observer),
+            None => searcher.find_path(self.starts, &self.goal),
+        }
+    }
+
+    /// Searches forward from the start nodes and backward from the goal at the same time. See
+    /// [`Bidirectional`].
+    ///
+    /// `visit_reverse_neighbors` must visit the nodes that have an edge leading *to* the given
+    /// node, with the weight of that edge.
+    ///
+    /// There is nothing to search backward from if the goal is a set of nodes or a predicate, so
+    /// `None` is returned in that case.
+    #[allow(clippy::type_complexity)]
+    pub fn find_path_bidirectional(
+        self,
+        reverse_heuristic: &'a dyn Fn(&Node) -> C,
+        visit_reverse_neighbors: &dyn Fn(&Node, &mut dyn FnMut(&Node, C)),
+    ) -> Option<Path<Node, C>> {
+        let Goal::Node(goal) = self.goal else {
+            return None;
+        };
+        Bidirectional {
+            starts: self.starts,
+            goal,
+            forward: from_visit(self.visit_neighbors),
+            backward: from_visit(visit_reverse_neighbors),
+            heuristic: self.heuristic,
+            reverse_heuristic,
+            observer: self.observer,
+        }
+        .find_path()
+    }
+}
+
+/// A* searching forward from the start nodes and backward from the goal at the same time, until
+/// the two searches meet. On large graphs without a good heuristic, this can expand far fewer
+/// nodes than searching in one direction.
+pub struct Bidirectional<'a, Node, C, F, B> {
+    pub starts: Vec<Node>,
+    pub goal: Node,
+    pub forward: F,
+    /// The graph with all of its edges reversed: the neighbors of a node are the nodes that have
+    /// an edge leading to it.
+    pub backward: B,
+    pub heuristic: &'a dyn Fn(&Node) -> C,
+    /// Estimates the cost of getting to the given node from the closest start node.
+    pub reverse_heuristic: &'a dyn Fn(&Node) -> C,
+    /// Notified of the steps of both searches.
+    pub observer: Option<&'a mut dyn Observer<Node, C>>,
+}
+
+impl<'a, Node, C, F, B> Bidirectional<'a, Node, C, F, B>
+where
+    Node: Clone + Eq + Hash,
+    C: Cost,
+    F: Graph<Node, C>,
+    B: Graph<Node, C>,
+{
+    pub fn find_path(self) -> Option<Path<Node, C>> {
+        let mut no_observer = ();
+        let observer = match self.observer {
+            Some(observer) => observer,
+            None => &mut no_observer,
+        };
+
+        let mut forward = Frontier::new(self.starts, self.heuristic, observer);
+        let mut backward = Frontier::new([self.goal.clone()], self.reverse_heuristic, observer);
+        // The cheapest path found so far, and the node the two searches met at on it.
+        let mut best: Option<(C, Node)> = forward
+            .g_score
+            .contains_key(&self.goal)
+            .then_some((C::ZERO, self.goal));
+        let mut expanded = 0;
+
+        while let (Some(forward_f), Some(backward_f)) =
+            (forward.min_f_score(), backward.min_f_score())
+        {
+            // Neither search can find anything cheaper than the smallest f-score in its open set.
+            if best
+                .as_ref()
+                .is_some_and(|&(cost, _)| cost <= forward_f.max(backward_f))
+            {
+                break;
+            }
+
+            expanded += 1;
+            if forward_f <= backward_f {
+                forward.expand(&backward, &self.forward, &mut best, observer);
+            } else {
+                backward.expand(&forward, &self.backward, &mut best, observer);
+            }
+        }
+
+        let (cost, meeting_point) = best?;
+        let mut nodes = reconstruct_path(&forward.came_from, meeting_point.clone());
+        let mut to_goal = reconstruct_path(&backward.came_from, meeting_point);
+        to_goal.reverse();
+        nodes.extend(to_goal.into_iter().skip(1));
+        Some(Path {
+            nodes,
+            cost,
+            expanded,
+        })
+    }
+}
+
+/// One direction of a bidirectional search.
+struct Frontier<'a, Node, C> {
+    heuristic: &'a dyn Fn(&Node) -> C,
+    open_set: BinaryHeap<Open<Node, C>>,
+    g_score: HashMap<Node, C>,
+    came_from: HashMap<Node, Node>,
+}
+
+impl<'a, Node, C> Frontier<'a, Node, C>
+where
+    Node: Clone + Eq + Hash,
+    C: Cost,
+{
+    fn new(
+        starts: impl IntoIterator<Item = Node>,
+        heuristic: &'a dyn Fn(&Node) -> C,
+        observer: &mut dyn Observer<Node, C>,
+    ) -> Self {
+        let mut frontier = Self {
+            heuristic,
+            open_set: BinaryHeap::new(),
+            g_score: HashMap::new(),
+            came_from: HashMap::new(),
+        };
+        for start in starts {
+            observer.open(&start, C::ZERO);
+            frontier.open_set.push(Open {
+                f_score: heuristic(&start),
+                g_score: C::ZERO,
+                node: start.clone(),
+            });
+            frontier.g_score.insert(start, C::ZERO);
+        }
+        frontier
+    }
+
+    /// The smallest f-score in the open set, skipping over stale entries.
+    fn min_f_score(&mut self) -> Option<C> {
+        while let Some(top) = self.open_set.peek() {
+            if top.g_score > self.g_score[&top.node] {
+                self.open_set.pop();
+            } else {
+                return Some(top.f_score);
+            }
+        }
+        None
+    }
+
+    /// Expands the node with the smallest f-score, updating `best` if the path to one of its
+    /// neighbors meets a path found by the other search, and the two make up a cheaper path than
+    /// the best one so far.
+    fn expand(
+        &mut self,
+        other: &Self,
+        graph: &impl Graph<Node, C>,
+        best: &mut Option<(C, Node)>,
+        observer: &mut dyn Observer<Node, C>,
+    ) {
+        let Some(Open {
+            g_score: current_g_score,
+            node: current,
+            ..
+        }) = self.open_set.pop()
+        else {
+            return;
+        };
+
+        observer.expand(&current);
+        for (neighbor, weight) in graph.neighbors(&current) {
+            let tentative_g_score = current_g_score + weight;
+            if self
+                .g_score
+                .get(&neighbor)
+                .is_some_and(|&old_g_score| old_g_score <= tentative_g_score)
+            {
+                continue;
+            }
+
+            observer.relax(&current, &neighbor, tentative_g_score);
+            observer.open(&neighbor, tentative_g_score);
+            if let Some(&other_g_score) = other.g_score.get(&neighbor) {
+                let cost = tentative_g_score + other_g_score;
+                if best.as_ref().is_none_or(|&(best_cost, _)| cost < best_cost) {
+                    *best = Some((cost, neighbor.clone()));
+                }
+            }
+            self.came_from.insert(neighbor.clone(), current.clone());
+            self.g_score.insert(neighbor.clone(), tentative_g_score);
+            self.open_set.push(Open {
+                f_score: tentative_g_score + (self.heuristic)(&neighbor),
+                g_score: tentative_g_score,
+                node: neighbor,
+            });
+        }
+    }
+}
+
+/// An A* search that can be run repeatedly over the same graph. Owns its graph and heuristic
+/// function, and reuses its allocations between searches.
+pub struct Searcher<Node, C, G, H> {
+    graph: G,
+    heuristic: H,
+    open_set: BinaryHeap<Open<Node, C>>,
+    came_from: HashMap<Node, Node>,
+    g_score: HashMap<Node, C>,
+}
+
+impl<Node, C, G, H> Searcher<Node, C, G, H>
+where
+    Node: Debug + Clone + Eq + Hash,
+    C: Cost,
+    G: Graph<Node, C>,
+    H: Fn(&Node) -> C,
+{
+    pub fn new(graph: G, heuristic: H) -> Self {
+        Self {
+            graph,
+            heuristic,
+            open_set: BinaryHeap::new(),
+            came_from: HashMap::new(),
+            g_score: HashMap::new(),
+        }
+    }
+
+    /// Finds the shortest path from any of the start nodes to the goal.
+    pub fn find_path(
+        &mut self,
+        starts: impl IntoIterator<Item = Node>,
+        goal: &Goal<Node>,
+    ) -> Option<Path<Node, C>> {
+        self.find_path_observed(starts, goal, &mut ())
+    }
+
+    /// Same as [`find_path`][Self::find_path], but notifies the observer of each step taken.
+    pub fn find_path_observed(
+        &mut self,
+        starts: impl IntoIterator<Item = Node>,
+        goal: &Goal<Node>,
+        observer: &mut dyn Observer<Node, C>,
+    ) -> Option<Path<Node, C>> {
+        let Self {
+            graph,
+            heuristic,
+            open_set,
+            came_from,
+            g_score,
+        } = self;
+        open_set.clear();
+        came_from.clear();
+        g_score.clear();
+
+        // Nodes are not removed from the open set when a shorter path to them is found; instead,
+        // the stale entries are skipped once they get popped.
+        for start in starts {
+            observer.open(&start, C::ZERO);
+            open_set.push(Open {
+                f_score: heuristic(&start),
+                g_score: C::ZERO,
+                node: start.clone(),
+            });
+            g_score.insert(start, C::ZERO);
+        }
+        let mut expanded = 0;
+
+        while let Some(Open {
+            g_score: current_g_score,
+            node: current,
+            ..
+        }) = open_set.pop()
+        {
+            if current_g_score > g_score[&current] {
+                continue;
+            }
+            if goal.is_reached(&current) {
+                return Some(Path {
+                    nodes: reconstruct_path(came_from, current),
+                    cost: current_g_score,
+                    expanded,
+                });
+            }
+
+            expanded += 1;
+            observer.expand(&current);
+            for (neighbor, weight) in graph.neighbors(&current) {
+                let tentative_g_score = current_g_score + weight;
+                if g_score
+                    .get(&neighbor)
+                    .is_none_or(|&old_g_score| tentative_g_score < old_g_score)
+                {
+                    observer.relax(&current, &neighbor, tentative_g_score);
+                    observer.open(&neighbor, tentative_g_score);
                     came_from.insert(neighbor.clone(), current.clone());
                     g_score.insert(neighbor.clone(), tentative_g_score);
-                    f_score.insert(
-                        neighbor.clone(),
-                        tentative_g_score + (self.heuristic)(neighbor),
-                    );
-                    open_set.insert(neighbor.clone());
+                    open_set.push(Open {
+                        f_score: tentative_g_score + heuristic(&neighbor),
+                        g_score: tentative_g_score,
+                        node: neighbor,
+                    });
                 }
-            });
+            }
         }
 
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{self, from_visit};
+
+    /// A directed graph stored as a list of edges, along with the same graph reversed.
+    struct EdgeList {
+        edges: Vec<(u32, u32, u32)>,
+    }
+
+    impl EdgeList {
+        fn forward(&self) -> impl Graph<u32, u32> + '_ {
+            from_visit(|&node: &u32, visit: &mut dyn FnMut(&u32, u32)| {
+                for &(from, to, weight) in &self.edges {
+                    if from == node {
+                        visit(&to, weight);
+                    }
+                }
+            })
+        }
+
+        fn backward(&self) -> impl Graph<u32, u32> + '_ {
+            from_visit(|&node: &u32, visit: &mut dyn FnMut(&u32, u32)| {
+                for &(from, to, weight) in &self.edges {
+                    if to == node {
+                        visit(&from, weight);
+                    }
+                }
+            })
+        }
+
+        /// A graph with pseudo-random edges, the same for the same seed.
+        fn random(seed: u64, node_count: u32, edge_count: usize) -> Self {
+            let mut state = seed;
+            let mut next = |bound: u32| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as u32 % bound
+            };
+            let edges = (0..edge_count)
+                .map(|_| (next(node_count), next(node_count), next(10)))
+                .collect();
+            Self { edges }
+        }
+
+        /// Panics unless the path is made of edges of the graph whose weights add up to `cost`.
+        fn check_path(&self, nodes: &[u32], cost: u32) {
+            let mut total = 0;
+            for pair in nodes.windows(2) {
+                total += self
+                    .edges
+                    .iter()
+                    .filter(|&&(from, to, _)| (from, to) == (pair[0], pair[1]))
+                    .map(|&(_, _, weight)| weight)
+                    .min()
+                    .unwrap_or_else(|| panic!("{pair:?} is not an edge"));
+            }
+            assert_eq!(total, cost, "weights along {nodes:?} do not add up");
+        }
+    }
+
+    fn bidirectional(graph: &EdgeList, starts: Vec<u32>, goal: u32) -> Option<Path<u32, u32>> {
+        let zero = |_: &u32| 0;
+        Bidirectional {
+            starts,
+            goal,
+            forward: graph.forward(),
+            backward: graph.backward(),
+            heuristic: &zero,
+            reverse_heuristic: &zero,
+            observer: None,
+        }
+        .find_path()
+    }
+
+    fn check_against_dijkstra(graph: &EdgeList, starts: Vec<u32>, goal: u32) {
+        let expected = graph::dijkstra(&graph.forward(), starts.clone(), &Goal::Node(goal));
+        let actual = bidirectional(graph, starts.clone(), goal);
+        assert_eq!(
+            actual.as_ref().map(|path| path.cost),
+            expected.as_ref().map(|path| path.cost),
+            "from {starts:?} to {goal} in {:?}",
+            graph.edges
+        );
+        if let Some(path) = actual {
+            assert!(starts.contains(&path.nodes[0]));
+            assert_eq!(path.nodes.last(), Some(&goal));
+            graph.check_path(&path.nodes, path.cost);
+        }
+    }
+
+    #[test]
+    fn bidirectional_meets_in_the_middle() {
+        let graph = EdgeList {
+            edges: vec![
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (0, 5, 2),
+                (5, 4, 5),
+                (4, 6, 1),
+            ],
+        };
+        let path = bidirectional(&graph, vec![0], 4).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 1, 2, 3, 4], 4));
+        let path = bidirectional(&graph, vec![0], 6).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 1, 2, 3, 4, 6], 5));
+    }
+
+    #[test]
+    fn bidirectional_start_is_goal() {
+        let graph = EdgeList {
+            edges: vec![(0, 1, 1), (1, 0, 1)],
+        };
+        let path = bidirectional(&graph, vec![0], 0).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0], 0));
+        let path = bidirectional(&graph, vec![1, 0], 0).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0], 0));
+    }
+
+    #[test]
+    fn bidirectional_unreachable_goal() {
+        let graph = EdgeList {
+            edges: vec![(0, 1, 1), (2, 1, 1), (3, 3, 1)],
+        };
+        assert!(bidirectional(&graph, vec![0], 2).is_none());
+        assert!(bidirectional(&graph, vec![0], 3).is_none());
+        assert!(bidirectional(&graph, vec![1], 0).is_none());
+        assert!(bidirectional(&graph, vec![], 0).is_none());
+    }
+
+    #[test]
+    fn bidirectional_matches_dijkstra() {
+        for seed in 0..200 {
+            let graph = EdgeList::random(seed, 12, 30);
+            for goal in 0..12 {
+                check_against_dijkstra(&graph, vec![seed as u32 % 12], goal);
+                check_against_dijkstra(&graph, vec![0, 5, 7], goal);
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_with_heuristics_matches_searcher() {
+        // A grid where moving right or down costs 1, and moving left or up costs 3. Manhattan
+        // distances do not overestimate in either direction.
+        let size = 6;
+        let mut edges = vec![];
+        for y in 0..size {
+            for x in 0..size {
+                let node = y * size + x;
+                if x + 1 < size {
+                    edges.push((node, node + 1, 1));
+                    edges.push((node + 1, node, 3));
+                }
+                if y + 1 < size {
+                    edges.push((node, node + size, 1));
+                    edges.push((node + size, node, 3));
+                }
+            }
+        }
+        let graph = EdgeList { edges };
+        let distance =
+            |a: u32, b: u32| (a % size).abs_diff(b % size) + (a / size).abs_diff(b / size);
+
+        for start in 0..size * size {
+            for goal in 0..size * size {
+                let heuristic = |&node: &u32| distance(node, goal);
+                let reverse_heuristic = |&node: &u32| distance(node, start);
+                let expected = Searcher::new(graph.forward(), heuristic)
+                    .find_path([start], &Goal::Node(goal))
+                    .unwrap();
+                let actual = Bidirectional {
+                    starts: vec![start],
+                    goal,
+                    forward: graph.forward(),
+                    backward: graph.backward(),
+                    heuristic: &heuristic,
+                    reverse_heuristic: &reverse_heuristic,
+                    observer: None,
+                }
+                .find_path()
+                .unwrap();
+                assert_eq!(actual.cost, expected.cost, "from {start} to {goal}");
+                graph.check_path(&actual.nodes, actual.cost);
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_needs_a_goal_node() {
+        let neighbors = |&node: &u32, visit: &mut dyn FnMut(&u32, u32)| visit(&(node + 1), 1);
+        let heuristic = |_: &u32| 0;
+        let search = |goal| AStar {
+            starts: vec![0],
+            goal,
+            heuristic: &heuristic,
+            visit_neighbors: &neighbors,
+            observer: None,
+        };
+        assert!(search(Goal::Predicate(&|&node| node == 3))
+            .find_path_bidirectional(&heuristic, &neighbors)
+            .is_none());
+        let path = search(Goal::Node(3))
+            .find_path_bidirectional(&heuristic, &|&node, visit| {
+                if let Some(previous) = node.checked_sub(1) {
+                    visit(&previous, 1)
+                }
+            })
+            .unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 1, 2, 3], 3));
+    }
+}
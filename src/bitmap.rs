@@ -1,9 +1,14 @@
-//! Bitmap storage and parsing.
+//! Bitmap storage, parsing, and rendering.
 
-use std::ops::{Index, IndexMut};
+use std::{
+    fmt::{Display, Write},
+    ops::{Index, IndexMut, Range},
+};
 
 use anyhow::{anyhow, bail};
+use owo_colors::{DynColor, OwoColorize};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bitmap<T> {
     pub elements: Vec<T>,
     pub width: u32,
@@ -40,26 +45,71 @@ impl<T> Bitmap<T> {
         (0..height).flat_map(move |y| (0..width).map(move |x| (x as i32, y as i32)))
     }
 
-    pub fn set(&mut self, position: (i32, i32), value: T) -> Result<(), OutOfBoundsError> {
+    /// Creates a bitmap of the same size, with each element computed from the position and element
+    /// at the same place in this one.
+    pub fn map<U>(&self, mut f: impl FnMut((i32, i32), &T) -> U) -> Bitmap<U>
+    where
+        U: Default,
+    {
+        Bitmap {
+            elements: self
+                .positions()
+                .zip(&self.elements)
+                .map(|(position, element)| f(position, element))
+                .collect(),
+            width: self.width,
+            height: self.height,
+            out_of_bounds: U::default(),
+        }
+    }
+
+    pub fn get(&self, position: (i32, i32)) -> Option<&T> {
+        if self.is_in_bounds(position) {
+            Some(&self.elements[self.flatten_index(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: (i32, i32)) -> Option<&mut T> {
         if self.is_in_bounds(position) {
             let index = self.flatten_index(position);
-            self.elements[index] = value;
-            Ok(())
+            Some(&mut self.elements[index])
         } else {
-            Err(OutOfBoundsError)
+            None
         }
     }
+
+    pub fn set(&mut self, position: (i32, i32), value: T) -> Result<(), OutOfBoundsError> {
+        self.update(position, |element| *element = value)
+    }
+
+    /// Modifies the element at the given position in place, returning what `f` returns.
+    pub fn update<R>(
+        &mut self,
+        position: (i32, i32),
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, OutOfBoundsError> {
+        self.get_mut(position).map(f).ok_or(OutOfBoundsError)
+    }
 }
 
 impl<T> Index<(i32, i32)> for Bitmap<T> {
     type Output = T;
 
     fn index(&self, index: (i32, i32)) -> &Self::Output {
-        if self.is_in_bounds(index) {
-            &self.elements[self.flatten_index(index)]
-        } else {
-            &self.out_of_bounds
-        }
+        self.get(index).unwrap_or(&self.out_of_bounds)
+    }
+}
+
+/// Unlike indexing immutably, which returns the `out_of_bounds` element, indexing mutably out of
+/// bounds panics. Use [`Bitmap::get_mut`] if the position may be out of bounds.
+impl<T> IndexMut<(i32, i32)> for Bitmap<T> {
+    fn index_mut(&mut self, index: (i32, i32)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("position {index:?} is out of bounds of the {width}x{height} bitmap")
+        })
     }
 }
 
@@ -75,8 +125,9 @@ where
         let mut height = 0;
         let mut elements = vec![];
         for (y, line) in s.lines().enumerate() {
+            let line_width = line.chars().count() as u32;
             if let Some(width) = width {
-                if line.len() as u32 != width {
+                if line_width != width {
                     bail!(
                         "all lines must be the same width (first line's width was {width}): {line}"
                     );
@@ -89,7 +140,7 @@ where
                         .ok_or_else(|| anyhow!("{c:?} is not a valid bitmap element"))?,
                 );
             }
-            width = Some(line.len() as u32);
+            width = Some(line_width);
             height += 1;
         }
         Ok((
@@ -104,8 +155,215 @@ where
     }
 }
 
+impl<T> Bitmap<T> {
+    /// Formats the bitmap as text, one line per row. The inverse of [`Bitmap::parse`]: for a
+    /// formatter that writes each element as the character its parser reads it from, parsing the
+    /// text gives back the same bitmap, as long as its `out_of_bounds` element is the default.
+    ///
+    /// The exception is a bitmap with no rows, whose width is lost, as it is formatted as empty
+    /// text. It is parsed back with a width of 0.
+    pub fn to_string_with<F>(&self, mut formatter: F) -> String
+    where
+        F: BitmapFormatter<Element = T>,
+    {
+        let mut out = String::with_capacity(self.elements.len() + self.height as usize);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let element = &self[(x as i32, y as i32)];
+                out.push(formatter.format_element((x, y), element));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
 pub trait BitmapParser {
     type Element: Default;
 
     fn parse_element(&mut self, position: (u32, u32), c: char) -> Option<Self::Element>;
 }
+
+/// The counterpart of [`BitmapParser`], used by [`Bitmap::to_string_with`].
+pub trait BitmapFormatter {
+    type Element;
+
+    fn format_element(&mut self, position: (u32, u32), element: &Self::Element) -> char;
+}
+
+/// Settings for rendering a bitmap as text, to be printed to the terminal.
+#[derive(Debug, Clone, Default)]
+pub struct Render {
+    /// The columns to render. All of them if `None`.
+    pub x_range: Option<Range<i32>>,
+    /// The rows to render. All of them if `None`.
+    pub y_range: Option<Range<i32>>,
+    /// Move the cursor to the top left corner of the terminal first, so that printing successive
+    /// frames animates them in place.
+    pub redraw: bool,
+}
+
+impl Render {
+    fn ranges<T>(&self, bitmap: &Bitmap<T>) -> (Range<i32>, Range<i32>) {
+        (
+            self.x_range.clone().unwrap_or(0..bitmap.width as i32),
+            self.y_range.clone().unwrap_or(0..bitmap.height as i32),
+        )
+    }
+
+    fn start(&self) -> String {
+        if self.redraw {
+            String::from("\x1B[1;1H")
+        } else {
+            String::new()
+        }
+    }
+
+    /// Renders each element as the text returned by `element`, one line per row.
+    pub fn text<T, D>(&self, bitmap: &Bitmap<T>, element: impl Fn(&T) -> D) -> String
+    where
+        D: Display,
+    {
+        let (x_range, y_range) = self.ranges(bitmap);
+        let mut out = self.start();
+        for y in y_range {
+            for x in x_range.clone() {
+                let _ = write!(out, "{}", element(&bitmap[(x, y)]));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Renders each element as a cell of the color returned by `color`. Two rows are packed into
+    /// each line using half blocks, so that cells come out roughly square. If there is an odd
+    /// number of rows, the bottom half of the last line is colored as out of bounds.
+    pub fn half_blocks<T, C>(&self, bitmap: &Bitmap<T>, color: impl Fn(&T) -> C) -> String
+    where
+        C: DynColor,
+    {
+        let (x_range, y_range) = self.ranges(bitmap);
+        let mut out = self.start();
+        for y in y_range.clone().step_by(2) {
+            for x in x_range.clone() {
+                let top = color(&bitmap[(x, y)]);
+                let bottom = if y + 1 < y_range.end {
+                    color(&bitmap[(x, y + 1)])
+                } else {
+                    color(&bitmap.out_of_bounds)
+                };
+                let _ = write!(out, "{}", "▄".color(bottom).on_color(top));
+            }
+            let _ = writeln!(out, "{}", "".default_color().on_default_color());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use owo_colors::AnsiColors;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    enum Tile {
+        #[default]
+        Empty,
+        Wall,
+        Snow,
+    }
+
+    struct TileFormat;
+
+    impl BitmapParser for TileFormat {
+        type Element = Tile;
+
+        fn parse_element(&mut self, _: (u32, u32), c: char) -> Option<Tile> {
+            match c {
+                '.' => Some(Tile::Empty),
+                '#' => Some(Tile::Wall),
+                '❄' => Some(Tile::Snow),
+                _ => None,
+            }
+        }
+    }
+
+    impl BitmapFormatter for TileFormat {
+        type Element = Tile;
+
+        fn format_element(&mut self, _: (u32, u32), element: &Tile) -> char {
+            match element {
+                Tile::Empty => '.',
+                Tile::Wall => '#',
+                Tile::Snow => '❄',
+            }
+        }
+    }
+
+    fn round_trip(bitmap: &Bitmap<Tile>) -> Bitmap<Tile> {
+        Bitmap::parse(TileFormat, &bitmap.to_string_with(TileFormat))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn format_then_parse() {
+        let mut bitmap = Bitmap::new(4, 3, Tile::Empty);
+        bitmap[(0, 0)] = Tile::Wall;
+        bitmap[(3, 1)] = Tile::Snow;
+        bitmap[(1, 2)] = Tile::Snow;
+        assert_eq!(bitmap.to_string_with(TileFormat), "#...\n...❄\n.❄..\n");
+        assert_eq!(round_trip(&bitmap), bitmap);
+    }
+
+    #[test]
+    fn parse_then_format() {
+        let text = "❄❄#\n.#❄\n";
+        let (bitmap, _) = Bitmap::parse(TileFormat, text).unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (3, 2));
+        assert_eq!(bitmap.to_string_with(TileFormat), text);
+    }
+
+    #[test]
+    fn round_trip_edge_cases() {
+        for (width, height) in [(0, 0), (1, 1), (5, 1), (1, 5), (0, 3)] {
+            let mut bitmap = Bitmap::new(width, height, Tile::Empty);
+            bitmap.elements.fill(Tile::Snow);
+            assert_eq!(round_trip(&bitmap), bitmap, "{width}x{height}");
+        }
+        // See the exception documented on `to_string_with`.
+        let bitmap = Bitmap::new(3, 0, Tile::Wall);
+        assert_eq!(round_trip(&bitmap), Bitmap::new(0, 0, Tile::Empty));
+    }
+
+    #[test]
+    fn half_blocks_crop_ignores_rows_below() {
+        let color = |tile: &Tile| match tile {
+            Tile::Empty => AnsiColors::Black,
+            Tile::Wall => AnsiColors::White,
+            Tile::Snow => AnsiColors::Cyan,
+        };
+        let (bitmap, _) = Bitmap::parse(TileFormat, "#.#\n❄❄❄\n.#.\n").unwrap();
+        let (first_row, _) = Bitmap::parse(TileFormat, "#.#\n").unwrap();
+        let cropped = Render {
+            y_range: Some(0..1),
+            ..Default::default()
+        };
+        // The bottom halves of the single row are out of bounds, rather than the snow below it.
+        assert_eq!(
+            cropped.half_blocks(&bitmap, color),
+            Render::default().half_blocks(&first_row, color)
+        );
+
+        let (last_rows, _) = Bitmap::parse(TileFormat, "❄❄❄\n.#.\n").unwrap();
+        let cropped = Render {
+            y_range: Some(1..3),
+            ..Default::default()
+        };
+        assert_eq!(
+            cropped.half_blocks(&bitmap, color),
+            Render::default().half_blocks(&last_rows, color)
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Pathfinding over the cells of a [`Bitmap`], with scores stored densely rather than in hash
+//! maps.
+
+use std::{
+    collections::{BinaryHeap, HashSet, VecDeque},
+    fmt::Write,
+};
+
+use owo_colors::OwoColorize;
+
+use crate::{
+    astar::{Cost, Goal, Observer, Open, Path},
+    bitmap::Bitmap,
+    graph::Graph,
+};
+
+/// Which cells count as neighbors of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Horizontal and vertical neighbors. A* uses the Manhattan distance as its heuristic.
+    Four,
+    /// Horizontal, vertical, and diagonal neighbors. A* uses the Chebyshev distance as its
+    /// heuristic.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [(i32, i32)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+
+    /// The smallest number of steps it takes to get from `a` to `b`.
+    pub fn distance(self, (ax, ay): (i32, i32), (bx, by): (i32, i32)) -> u32 {
+        let (dx, dy) = (ax.abs_diff(bx), ay.abs_diff(by));
+        match self {
+            Connectivity::Four => dx + dy,
+            Connectivity::Eight => dx.max(dy),
+        }
+    }
+}
+
+/// Searches for paths between cells of a bitmap. Can be used for multiple searches over the same
+/// bitmap, reusing its allocations.
+///
+/// Whether it's possible to step from one cell to another, and at what cost, is decided by the
+/// `passable` function, which is given the elements of both cells.
+pub struct BitmapSearch<'a, T, C, P> {
+    bitmap: &'a Bitmap<T>,
+    connectivity: Connectivity,
+    passable: P,
+    open_set: BinaryHeap<Open<usize, C>>,
+    g_score: Vec<Option<C>>,
+    /// Like `g_score`, but for BFS, which counts steps instead of costs.
+    steps: Vec<Option<usize>>,
+    came_from: Vec<Option<usize>>,
+}
+
+impl<'a, T, C, P> BitmapSearch<'a, T, C, P>
+where
+    C: Cost,
+    P: Fn(&T, &T) -> Option<C>,
+{
+    pub fn new(bitmap: &'a Bitmap<T>, connectivity: Connectivity, passable: P) -> Self {
+        Self {
+            bitmap,
+            connectivity,
+            passable,
+            open_set: BinaryHeap::new(),
+            g_score: vec![],
+            steps: vec![],
+            came_from: vec![],
+        }
+    }
+
+    fn reset(&mut self) {
+        let len = self.bitmap.elements.len();
+        self.open_set.clear();
+        self.g_score.clear();
+        self.g_score.resize(len, None);
+        self.steps.clear();
+        self.steps.resize(len, None);
+        self.came_from.clear();
+        self.came_from.resize(len, None);
+    }
+
+    fn reconstruct_path(&self, mut current: usize) -> Vec<(i32, i32)> {
+        let mut path = vec![position(self.bitmap, current)];
+        while let Some(previous) = self.came_from[current] {
+            current = previous;
+            path.push(position(self.bitmap, current));
+        }
+        path.reverse();
+        path
+    }
+
+    /// Finds the cheapest path from any of the start cells to the goal cell. Steps are assumed to
+    /// cost at least 1, so that the heuristic does not overestimate.
+    pub fn a_star(
+        &mut self,
+        starts: impl IntoIterator<Item = (i32, i32)>,
+        goal: (i32, i32),
+    ) -> Option<Path<(i32, i32), C>> {
+        self.a_star_observed(starts, goal, &mut ())
+    }
+
+    /// Same as [`a_star`][Self::a_star], but notifies the observer of each step taken.
+    pub fn a_star_observed(
+        &mut self,
+        starts: impl IntoIterator<Item = (i32, i32)>,
+        goal: (i32, i32),
+        observer: &mut dyn Observer<(i32, i32), C>,
+    ) -> Option<Path<(i32, i32), C>> {
+        self.reset();
+        let connectivity = self.connectivity;
+        let heuristic = |position| C::from_steps(connectivity.distance(position, goal));
+
+        for start in starts
+            .into_iter()
+            .filter(|&start| self.bitmap.is_in_bounds(start))
+        {
+            let index = self.bitmap.flatten_index(start);
+            observer.open(&start, C::ZERO);
+            self.g_score[index] = Some(C::ZERO);
+            self.open_set.push(Open {
+                f_score: heuristic(start),
+                g_score: C::ZERO,
+                node: index,
+            });
+        }
+
+        let mut expanded = 0;
+        while let Some(Open {
+            g_score: current_g_score,
+            node: current,
+            ..
+        }) = self.open_set.pop()
+        {
+            if self.g_score[current].is_some_and(|g_score| current_g_score > g_score) {
+                continue;
+            }
+            if position(self.bitmap, current) == goal {
+                return Some(Path {
+                    nodes: self.reconstruct_path(current),
+                    cost: current_g_score,
+                    expanded,
+                });
+            }
+
+            expanded += 1;
+            observer.expand(&position(self.bitmap, current));
+            let Self {
+                bitmap,
+                open_set,
+                g_score,
+                came_from,
+                ..
+            } = self;
+            visit_neighbors(
+                bitmap,
+                connectivity,
+                &self.passable,
+                current,
+                |neighbor, cost| {
+                    let tentative_g_score = current_g_score + cost;
+                    if g_score[neighbor].is_none_or(|old_g_score| tentative_g_score < old_g_score) {
+                        let (from, to) = (position(bitmap, current), position(bitmap, neighbor));
+                        observer.relax(&from, &to, tentative_g_score);
+                        observer.open(&to, tentative_g_score);
+                        g_score[neighbor] = Some(tentative_g_score);
+                        came_from[neighbor] = Some(current);
+                        open_set.push(Open {
+                            f_score: tentative_g_score + heuristic(to),
+                            g_score: tentative_g_score,
+                            node: neighbor,
+                        });
+                    }
+                },
+            );
+        }
+        None
+    }
+
+    /// Finds the path with the fewest steps from any of the start cells to the goal. The costs
+    /// returned by `passable` are ignored, other than to tell whether a step can be taken at all.
+    pub fn bfs(
+        &mut self,
+        starts: impl IntoIterator<Item = (i32, i32)>,
+        goal: &Goal<(i32, i32)>,
+    ) -> Option<Path<(i32, i32), usize>> {
+        self.reset();
+        let mut queue = VecDeque::new();
+        for start in starts
+            .into_iter()
+            .filter(|&start| self.bitmap.is_in_bounds(start))
+        {
+            let index = self.bitmap.flatten_index(start);
+            if self.steps[index].is_none() {
+                self.steps[index] = Some(0);
+                queue.push_back(index);
+            }
+        }
+
+        let mut expanded = 0;
+        while let Some(current) = queue.pop_front() {
+            if goal.is_reached(&position(self.bitmap, current)) {
+                return Some(Path {
+                    nodes: self.reconstruct_path(current),
+                    cost: self.steps[current].unwrap_or_default(),
+                    expanded,
+                });
+            }
+
+            expanded += 1;
+            let Self {
+                bitmap,
+                connectivity,
+                steps,
+                came_from,
+                ..
+            } = self;
+            let next_steps = steps[current].map(|steps| steps + 1);
+            visit_neighbors(
+                bitmap,
+                *connectivity,
+                &self.passable,
+                current,
+                |neighbor, _| {
+                    if steps[neighbor].is_none() {
+                        steps[neighbor] = next_steps;
+                        came_from[neighbor] = Some(current);
+                        queue.push_back(neighbor);
+                    }
+                },
+            );
+        }
+        None
+    }
+}
+
+/// The bitmap seen as a graph, so that it can be searched with the algorithms in
+/// [`graph`][crate::graph] and [`astar`][crate::astar] too.
+impl<'a, T, C, P> Graph<(i32, i32), C> for BitmapSearch<'a, T, C, P>
+where
+    P: Fn(&T, &T) -> Option<C>,
+{
+    fn neighbors(&self, &(x, y): &(i32, i32)) -> impl Iterator<Item = ((i32, i32), C)> {
+        let here = &self.bitmap[(x, y)];
+        self.connectivity
+            .offsets()
+            .iter()
+            .filter_map(move |&(dx, dy)| {
+                let neighbor = (x + dx, y + dy);
+                if !self.bitmap.is_in_bounds(neighbor) {
+                    return None;
+                }
+                let cost = (self.passable)(here, &self.bitmap[neighbor])?;
+                Some((neighbor, cost))
+            })
+    }
+}
+
+/// Records which cells a search explored, to render them afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct GridTrace {
+    pub explored: HashSet<(i32, i32)>,
+    /// Cells that were opened but not yet explored when the search ended.
+    pub frontier: HashSet<(i32, i32)>,
+}
+
+impl<C> Observer<(i32, i32), C> for GridTrace {
+    fn open(&mut self, node: &(i32, i32), _g_score: C) {
+        self.frontier.insert(*node);
+    }
+
+    fn expand(&mut self, node: &(i32, i32)) {
+        self.frontier.remove(node);
+        self.explored.insert(*node);
+    }
+}
+
+impl GridTrace {
+    /// Renders the bitmap with each element drawn as the given character, and the cells on the
+    /// path, explored, and on the frontier highlighted in different colors.
+    pub fn render<T>(
+        &self,
+        bitmap: &Bitmap<T>,
+        path: &[(i32, i32)],
+        element: impl Fn(&T) -> char,
+    ) -> String {
+        let path: HashSet<_> = path.iter().copied().collect();
+        let mut out = String::new();
+        for y in 0..bitmap.height as i32 {
+            for x in 0..bitmap.width as i32 {
+                let c = element(&bitmap[(x, y)]);
+                let _ = if path.contains(&(x, y)) {
+                    write!(out, "{}", c.black().on_yellow())
+                } else if self.frontier.contains(&(x, y)) {
+                    write!(out, "{}", c.black().on_green())
+                } else if self.explored.contains(&(x, y)) {
+                    write!(out, "{}", c.on_blue())
+                } else {
+                    write!(out, "{c}")
+                };
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn position<T>(bitmap: &Bitmap<T>, index: usize) -> (i32, i32) {
+    let width = bitmap.width as usize;
+    ((index % width) as i32, (index / width) as i32)
+}
+
+/// Calls `visit` with the index of each neighbor the cell can step to, and the cost of the step.
+fn visit_neighbors<T, C>(
+    bitmap: &Bitmap<T>,
+    connectivity: Connectivity,
+    passable: impl Fn(&T, &T) -> Option<C>,
+    index: usize,
+    mut visit: impl FnMut(usize, C),
+) {
+    let (x, y) = position(bitmap, index);
+    let here = &bitmap.elements[index];
+    for &(dx, dy) in connectivity.offsets() {
+        let neighbor = (x + dx, y + dy);
+        if bitmap.is_in_bounds(neighbor) {
+            let neighbor = bitmap.flatten_index(neighbor);
+            if let Some(cost) = passable(here, &bitmap.elements[neighbor]) {
+                visit(neighbor, cost);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph;
+
+    /// Cells are walls (`#`), or digits giving the cost of stepping onto them.
+    fn bitmap(rows: &[&str]) -> Bitmap<char> {
+        Bitmap {
+            elements: rows.iter().flat_map(|row| row.chars()).collect(),
+            width: rows[0].len() as u32,
+            height: rows.len() as u32,
+            out_of_bounds: '#',
+        }
+    }
+
+    fn passable(_: &char, to: &char) -> Option<u32> {
+        to.to_digit(10)
+    }
+
+    const ROWS: &[&str] = &["11111", "1###1", "191#1", "1####", "1#1#9"];
+
+    #[test]
+    fn bfs_matches_graph_bfs() {
+        let bitmap = bitmap(ROWS);
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let mut search = BitmapSearch::new(&bitmap, connectivity, passable);
+            for starts in [vec![(0, 0)], vec![(0, 0), (2, 4)]] {
+                for goal in bitmap.positions() {
+                    let goal = Goal::Node(goal);
+                    let actual = search.bfs(starts.clone(), &goal);
+                    let expected = graph::bfs(&search, starts.clone(), &goal);
+                    assert_eq!(
+                        actual.as_ref().map(|path| path.cost),
+                        expected.as_ref().map(|path| path.cost),
+                    );
+                    if let Some(path) = actual {
+                        assert_eq!(path.nodes.len(), path.cost + 1);
+                        assert!(starts.contains(&path.nodes[0]));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_star_matches_dijkstra() {
+        let bitmap = bitmap(ROWS);
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let mut search = BitmapSearch::new(&bitmap, connectivity, passable);
+            for goal in bitmap.positions() {
+                let actual = search.a_star([(0, 0)], goal);
+                let expected = graph::dijkstra(&search, [(0, 0)], &Goal::Node(goal));
+                assert_eq!(
+                    actual.as_ref().map(|path| path.cost),
+                    expected.as_ref().map(|path| path.cost),
+                );
+                if let Some(path) = actual {
+                    assert_eq!(path.nodes.first(), Some(&(0, 0)));
+                    assert_eq!(path.nodes.last(), Some(&goal));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn paths_through_cheap_cells() {
+        let bitmap = bitmap(ROWS);
+        let mut search = BitmapSearch::new(&bitmap, Connectivity::Four, passable);
+        let path = search.a_star([(0, 2)], (2, 2)).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![(0, 2), (1, 2), (2, 2)], 10));
+        let path = search.a_star([(0, 0)], (4, 2)).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes.len(), 7);
+    }
+
+    #[test]
+    fn small_cost_types() {
+        let bitmap = bitmap(ROWS);
+        let mut search = BitmapSearch::new(&bitmap, Connectivity::Eight, |_, to: &char| {
+            to.to_digit(10).map(|cost| cost as u8)
+        });
+        let path = search.a_star([(0, 0)], (4, 2)).unwrap();
+        assert_eq!(path.cost, 5_u8);
+    }
+
+    #[test]
+    fn unreachable_goals() {
+        let bitmap = bitmap(ROWS);
+        let mut search = BitmapSearch::new(&bitmap, Connectivity::Four, passable);
+        // Walled off from the rest of the bitmap.
+        assert!(search.a_star([(0, 0)], (2, 4)).is_none());
+        assert!(search.a_star([(0, 0)], (4, 4)).is_none());
+        assert!(search.bfs([(0, 0)], &Goal::Node((2, 4))).is_none());
+        // Walls and cells outside the bitmap.
+        assert!(search.a_star([(0, 0)], (1, 1)).is_none());
+        assert!(search.a_star([(0, 0)], (5, 0)).is_none());
+        assert!(search.bfs([(-1, 0)], &Goal::Node((0, 0))).is_none());
+    }
+}
@@ -0,0 +1,70 @@
+//! Debug flags that days can be run with.
+
+use std::fmt;
+
+/// A debug flag, as shown in `--help` and accepted by `--debug`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugFlagInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl fmt::Display for DebugFlagInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.description.trim())
+    }
+}
+
+/// Implemented by the debug flag enums generated by [`debug_flags!`][crate::debug_flags].
+pub trait DebugFlag: Copy {
+    const ALL: &'static [DebugFlagInfo];
+
+    fn name(self) -> &'static str;
+}
+
+/// Declares an enum of debug flags. Each flag must have a single-line doc comment, which is used
+/// as its description in `--help`.
+///
+/// ```ignore
+/// aoc::debug_flags! {
+///     enum Flag {
+///         /// Print the cave after each step of the simulation.
+///         Cave = "cave",
+///     }
+/// }
+///
+/// if challenge.debug(Flag::Cave) { /* ... */ }
+/// ```
+#[macro_export]
+macro_rules! debug_flags {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                #[doc = $description:literal]
+                $variant:ident = $flag:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::debug::DebugFlag for $name {
+            const ALL: &'static [$crate::debug::DebugFlagInfo] = &[$(
+                $crate::debug::DebugFlagInfo {
+                    name: $flag,
+                    description: $description,
+                }
+            ),*];
+
+            fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $flag),*
+                }
+            }
+        }
+    };
+}
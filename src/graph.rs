@@ -0,0 +1,538 @@
+//! Graphs, and search algorithms for when there is no useful heuristic to guide [`AStar`] with.
+//!
+//! All the searches in `aoc` work on any [`Graph`]. Code that visits neighbors through a
+//! callback, like [`AStar`] takes, can be adapted with [`from_visit`].
+//!
+//! [`AStar`]: crate::astar::AStar
+
+use std::{
+    cell::RefCell,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::astar::{reconstruct_path, Cost, Goal, Open, Path};
+
+/// A directed graph with edges weighted by costs of type `C`.
+pub trait Graph<Node, C> {
+    /// The nodes that have an edge leading to them from `node`, along with the edges' weights.
+    fn neighbors(&self, node: &Node) -> impl Iterator<Item = (Node, C)>;
+}
+
+impl<Node, C, G> Graph<Node, C> for &G
+where
+    G: Graph<Node, C> + ?Sized,
+{
+    fn neighbors(&self, node: &Node) -> impl Iterator<Item = (Node, C)> {
+        (**self).neighbors(node)
+    }
+}
+
+/// A graph whose neighbors are visited through a callback. See [`from_visit`].
+///
+/// The callback cannot be turned into an iterator directly, so the neighbors are collected into a
+/// buffer first. The buffer is kept between calls, so that expanding a node does not allocate.
+pub struct VisitNeighbors<Node, C, F> {
+    visit_neighbors: F,
+    buffer: RefCell<Vec<(Node, C)>>,
+}
+
+impl<Node, C, F> Graph<Node, C> for VisitNeighbors<Node, C, F>
+where
+    Node: Clone,
+    F: Fn(&Node, &mut dyn FnMut(&Node, C)),
+{
+    fn neighbors(&self, node: &Node) -> impl Iterator<Item = (Node, C)> {
+        // If an iterator returned earlier is still alive, this gets an empty buffer and allocates
+        // a new one, as it would have without the buffer.
+        let mut neighbors = self.buffer.take();
+        (self.visit_neighbors)(node, &mut |neighbor, weight| {
+            neighbors.push((neighbor.clone(), weight))
+        });
+        // Reversed so that popping yields the neighbors in the order they were visited in.
+        neighbors.reverse();
+        BufferedNeighbors {
+            neighbors,
+            home: &self.buffer,
+        }
+    }
+}
+
+/// Yields neighbors from a [`VisitNeighbors`] buffer, and returns the buffer when dropped.
+struct BufferedNeighbors<'a, Node, C> {
+    neighbors: Vec<(Node, C)>,
+    home: &'a RefCell<Vec<(Node, C)>>,
+}
+
+impl<Node, C> Iterator for BufferedNeighbors<'_, Node, C> {
+    type Item = (Node, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.neighbors.pop()
+    }
+}
+
+impl<Node, C> Drop for BufferedNeighbors<'_, Node, C> {
+    fn drop(&mut self) {
+        let mut neighbors = std::mem::take(&mut self.neighbors);
+        neighbors.clear();
+        self.home.replace(neighbors);
+    }
+}
+
+/// Turns a function that calls `visit` with each neighbor of a node and the weight of the edge
+/// leading to it into a [`Graph`].
+pub fn from_visit<Node, C, F>(visit_neighbors: F) -> VisitNeighbors<Node, C, F>
+where
+    F: Fn(&Node, &mut dyn FnMut(&Node, C)),
+{
+    VisitNeighbors {
+        visit_neighbors,
+        buffer: RefCell::new(vec![]),
+    }
+}
+
+/// The shortest distances from the start nodes to all the nodes reached by a search.
+#[derive(Debug, Clone)]
+pub struct DistanceMap<Node, Distance> {
+    pub distances: HashMap<Node, Distance>,
+    /// The node each node was reached from on its shortest path. Start nodes have no entry.
+    pub came_from: HashMap<Node, Node>,
+}
+
+impl<Node, Distance> DistanceMap<Node, Distance>
+where
+    Node: Clone + Eq + Hash,
+    Distance: Copy,
+{
+    pub fn distance(&self, node: &Node) -> Option<Distance> {
+        self.distances.get(node).copied()
+    }
+
+    /// The shortest path from a start node to the given node, including both ends. `None` if the
+    /// node was not reached.
+    pub fn path_to(&self, node: &Node) -> Option<Vec<Node>> {
+        self.distances
+            .contains_key(node)
+            .then(|| reconstruct_path(&self.came_from, node.clone()))
+    }
+}
+
+/// Searches until `goal` is reached, or the whole graph has been explored if there is no goal.
+/// Returns the distances found so far, and the goal node reached along with how many nodes were
+/// expanded to reach it.
+fn bfs_search<Node, C>(
+    graph: &impl Graph<Node, C>,
+    starts: impl IntoIterator<Item = Node>,
+    goal: Option<&Goal<Node>>,
+) -> (DistanceMap<Node, usize>, Option<(Node, usize)>)
+where
+    Node: Clone + Eq + Hash,
+{
+    let mut map = DistanceMap {
+        distances: HashMap::new(),
+        came_from: HashMap::new(),
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !map.distances.contains_key(&start) {
+            map.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    let mut expanded = 0;
+    while let Some(current) = queue.pop_front() {
+        if goal.is_some_and(|goal| goal.is_reached(&current)) {
+            return (map, Some((current, expanded)));
+        }
+
+        expanded += 1;
+        let distance = map.distances[&current] + 1;
+        for (neighbor, _) in graph.neighbors(&current) {
+            if !map.distances.contains_key(&neighbor) {
+                map.distances.insert(neighbor.clone(), distance);
+                map.came_from.insert(neighbor.clone(), current.clone());
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    (map, None)
+}
+
+/// Same as [`bfs_search`], but with weighted edges.
+fn dijkstra_search<Node, C>(
+    graph: &impl Graph<Node, C>,
+    starts: impl IntoIterator<Item = Node>,
+    goal: Option<&Goal<Node>>,
+) -> (DistanceMap<Node, C>, Option<(Node, usize)>)
+where
+    Node: Clone + Eq + Hash,
+    C: Cost,
+{
+    let mut map = DistanceMap {
+        distances: HashMap::new(),
+        came_from: HashMap::new(),
+    };
+    let mut open_set = BinaryHeap::new();
+    for start in starts {
+        map.distances.insert(start.clone(), C::ZERO);
+        open_set.push(Open {
+            f_score: C::ZERO,
+            g_score: C::ZERO,
+            node: start,
+        });
+    }
+
+    let mut expanded = 0;
+    while let Some(Open {
+        g_score: distance,
+        node: current,
+        ..
+    }) = open_set.pop()
+    {
+        if distance > map.distances[&current] {
+            continue;
+        }
+        if goal.is_some_and(|goal| goal.is_reached(&current)) {
+            return (map, Some((current, expanded)));
+        }
+
+        expanded += 1;
+        for (neighbor, weight) in graph.neighbors(&current) {
+            let tentative_distance = distance + weight;
+            if map
+                .distance(&neighbor)
+                .is_none_or(|old_distance| tentative_distance < old_distance)
+            {
+                map.distances.insert(neighbor.clone(), tentative_distance);
+                map.came_from.insert(neighbor.clone(), current.clone());
+                open_set.push(Open {
+                    f_score: tentative_distance,
+                    g_score: tentative_distance,
+                    node: neighbor,
+                });
+            }
+        }
+    }
+    (map, None)
+}
+
+fn path_to_goal<Node, Distance>(
+    (map, reached): (DistanceMap<Node, Distance>, Option<(Node, usize)>),
+) -> Option<Path<Node, Distance>>
+where
+    Node: Clone + Eq + Hash,
+    Distance: Copy,
+{
+    let (goal, expanded) = reached?;
+    Some(Path {
+        cost: map.distances[&goal],
+        nodes: map.path_to(&goal)?,
+        expanded,
+    })
+}
+
+/// Finds the path with the fewest edges from any of the start nodes to the goal, using
+/// breadth-first search. The cost of the path is its number of edges; the weights of the edges
+/// are ignored.
+pub fn bfs<Node, C>(
+    graph: &impl Graph<Node, C>,
+    starts: impl IntoIterator<Item = Node>,
+    goal: &Goal<Node>,
+) -> Option<Path<Node, usize>>
+where
+    Node: Clone + Eq + Hash,
+{
+    path_to_goal(bfs_search(graph, starts, Some(goal)))
+}
+
+/// Finds the number of edges on the shortest path to every node reachable from the start nodes.
+pub fn bfs_distances<Node, C>(
+    graph: &impl Graph<Node, C>,
+    starts: impl IntoIterator<Item = Node>,
+) -> DistanceMap<Node, usize>
+where
+    Node: Clone + Eq + Hash,
+{
+    bfs_search(graph, starts, None).0
+}
+
+/// Finds the cheapest path from any of the start nodes to the goal, using Dijkstra's algorithm.
+/// Weights must not be negative.
+pub fn dijkstra<Node, C>(
+    graph: &impl Graph<Node, C>,
+    starts: impl IntoIterator<Item = Node>,
+    goal: &Goal<Node>,
+) -> Option<Path<Node, C>>
+where
+    Node: Clone + Eq + Hash,
+    C: Cost,
+{
+    path_to_goal(dijkstra_search(graph, starts, Some(goal)))
+}
+
+/// Finds the cost of the cheapest path to every node reachable from the start nodes.
+pub fn dijkstra_distances<Node, C>(
+    graph: &impl Graph<Node, C>,
+    starts: impl IntoIterator<Item = Node>,
+) -> DistanceMap<Node, C>
+where
+    Node: Clone + Eq + Hash,
+    C: Cost,
+{
+    dijkstra_search(graph, starts, None).0
+}
+
+/// Breadth-first search over a graph visited through a callback. Every edge is treated as having
+/// a weight of 1; the weights passed to `visit` are ignored.
+pub struct Bfs<'a, Node, C> {
+    pub starts: Vec<Node>,
+    #[allow(clippy::type_complexity)]
+    pub visit_neighbors: &'a dyn Fn(&Node, &mut dyn FnMut(&Node, C)),
+}
+
+impl<'a, Node, C> Bfs<'a, Node, C>
+where
+    Node: Clone + Eq + Hash,
+{
+    /// See [`bfs`].
+    pub fn find_path(&self, goal: &Goal<Node>) -> Option<Path<Node, usize>> {
+        bfs(&from_visit(self.visit_neighbors), self.starts.clone(), goal)
+    }
+
+    /// See [`bfs_distances`].
+    pub fn distance_map(&self) -> DistanceMap<Node, usize> {
+        bfs_distances(&from_visit(self.visit_neighbors), self.starts.clone())
+    }
+}
+
+/// Dijkstra's algorithm over a graph visited through a callback.
+pub struct Dijkstra<'a, Node, C> {
+    pub starts: Vec<Node>,
+    #[allow(clippy::type_complexity)]
+    pub visit_neighbors: &'a dyn Fn(&Node, &mut dyn FnMut(&Node, C)),
+}
+
+impl<'a, Node, C> Dijkstra<'a, Node, C>
+where
+    Node: Clone + Eq + Hash,
+    C: Cost,
+{
+    /// See [`dijkstra`].
+    pub fn find_path(&self, goal: &Goal<Node>) -> Option<Path<Node, C>> {
+        dijkstra(&from_visit(self.visit_neighbors), self.starts.clone(), goal)
+    }
+
+    /// See [`dijkstra_distances`].
+    pub fn distance_map(&self) -> DistanceMap<Node, C> {
+        dijkstra_distances(&from_visit(self.visit_neighbors), self.starts.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::astar::Searcher;
+
+    /// A small weighted graph. `f` leads straight to `e`, but cannot be reached from `a`; `g` has
+    /// no edges at all.
+    struct TestGraph(HashMap<char, Vec<(char, u32)>>);
+
+    impl TestGraph {
+        fn new() -> Self {
+            let mut edges: HashMap<char, Vec<(char, u32)>> = HashMap::new();
+            for (from, to, weight) in [
+                ('a', 'b', 1),
+                ('a', 'c', 4),
+                ('b', 'c', 2),
+                ('b', 'd', 5),
+                ('c', 'd', 1),
+                ('d', 'e', 3),
+                ('f', 'e', 1),
+            ] {
+                edges.entry(from).or_default().push((to, weight));
+            }
+            Self(edges)
+        }
+
+        fn visit(&self, node: &char, visit: &mut dyn FnMut(&char, u32)) {
+            for (neighbor, weight) in self.0.get(node).into_iter().flatten() {
+                visit(neighbor, *weight);
+            }
+        }
+
+        /// Panics if any two consecutive nodes of the path are not connected by an edge.
+        fn check_edges(&self, nodes: &[char]) {
+            for pair in nodes.windows(2) {
+                assert!(
+                    self.neighbors(&pair[0])
+                        .any(|(neighbor, _)| neighbor == pair[1]),
+                    "{pair:?} is not an edge"
+                );
+            }
+        }
+    }
+
+    impl Graph<char, u32> for TestGraph {
+        fn neighbors(&self, node: &char) -> impl Iterator<Item = (char, u32)> {
+            self.0.get(node).into_iter().flatten().copied()
+        }
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        let graph = TestGraph::new();
+        let path = dijkstra(&graph, ['a'], &Goal::Node('e')).unwrap();
+        assert_eq!(path.nodes, ['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(path.cost, 7);
+    }
+
+    #[test]
+    fn bfs_finds_path_with_fewest_edges() {
+        let graph = TestGraph::new();
+        let path = bfs(&graph, ['a'], &Goal::Node('e')).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes.len(), 4);
+        assert_eq!((path.nodes[0], path.nodes[3]), ('a', 'e'));
+        graph.check_edges(&path.nodes);
+    }
+
+    #[test]
+    fn multiple_starts() {
+        let graph = TestGraph::new();
+        let path = dijkstra(&graph, ['a', 'f'], &Goal::Node('e')).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec!['f', 'e'], 1));
+        let path = bfs(&graph, ['a', 'f'], &Goal::Node('e')).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec!['f', 'e'], 1));
+    }
+
+    #[test]
+    fn goal_sets_and_predicates() {
+        let graph = TestGraph::new();
+        let goals = Goal::Set(HashSet::from(['d', 'e']));
+        let path = dijkstra(&graph, ['a'], &goals).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec!['a', 'b', 'c', 'd'], 4));
+        let path = bfs(&graph, ['a'], &Goal::Predicate(&|&node| node > 'b')).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec!['a', 'c'], 1));
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let graph = TestGraph::new();
+        let path = dijkstra(&graph, ['a'], &Goal::Node('a')).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec!['a'], 0));
+        let path = bfs(&graph, ['a'], &Goal::Node('a')).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec!['a'], 0));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let graph = TestGraph::new();
+        assert!(dijkstra(&graph, ['a'], &Goal::Node('f')).is_none());
+        assert!(dijkstra(&graph, ['a'], &Goal::Node('g')).is_none());
+        assert!(bfs(&graph, ['a'], &Goal::Node('g')).is_none());
+        assert!(dijkstra(&graph, [], &Goal::Node('a')).is_none());
+    }
+
+    #[test]
+    fn distance_maps() {
+        let graph = TestGraph::new();
+        let map = dijkstra_distances(&graph, ['a']);
+        let distances: Vec<_> = "abcdefg".chars().map(|node| map.distance(&node)).collect();
+        assert_eq!(
+            distances,
+            [Some(0), Some(1), Some(3), Some(4), Some(7), None, None]
+        );
+        assert_eq!(map.path_to(&'e').unwrap(), ['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(map.path_to(&'a').unwrap(), ['a']);
+        assert!(map.path_to(&'g').is_none());
+
+        let map = bfs_distances(&graph, ['a']);
+        let distances: Vec<_> = "abcdefg".chars().map(|node| map.distance(&node)).collect();
+        assert_eq!(
+            distances,
+            [Some(0), Some(1), Some(1), Some(2), Some(3), None, None]
+        );
+        let path = map.path_to(&'e').unwrap();
+        assert_eq!(path.len(), 4);
+        graph.check_edges(&path);
+    }
+
+    #[test]
+    fn callback_searches_match_graph_searches() {
+        let graph = TestGraph::new();
+        let visit_neighbors =
+            |node: &char, visit: &mut dyn FnMut(&char, u32)| graph.visit(node, visit);
+        let bfs_search = Bfs {
+            starts: vec!['a'],
+            visit_neighbors: &visit_neighbors,
+        };
+        let dijkstra_search = Dijkstra {
+            starts: vec!['a'],
+            visit_neighbors: &visit_neighbors,
+        };
+        for goal in "abcdefg".chars() {
+            let goal = Goal::Node(goal);
+            let expected = bfs(&graph, ['a'], &goal).map(|path| path.cost);
+            assert_eq!(bfs_search.find_path(&goal).map(|path| path.cost), expected);
+            let expected = dijkstra(&graph, ['a'], &goal).map(|path| path.cost);
+            assert_eq!(
+                dijkstra_search.find_path(&goal).map(|path| path.cost),
+                expected
+            );
+        }
+        assert_eq!(
+            bfs_search.distance_map().distances,
+            bfs_distances(&graph, ['a']).distances
+        );
+        assert_eq!(
+            dijkstra_search.distance_map().distances,
+            dijkstra_distances(&graph, ['a']).distances
+        );
+    }
+
+    #[test]
+    fn visit_adapter_reuses_its_buffer() {
+        let graph = TestGraph::new();
+        let adapter =
+            from_visit(|node: &char, visit: &mut dyn FnMut(&char, u32)| graph.visit(node, visit));
+        let neighbors: Vec<_> = adapter.neighbors(&'b').collect();
+        assert_eq!(neighbors, graph.0[&'b']);
+        let capacity = adapter.buffer.borrow().capacity();
+        assert!(capacity >= 2);
+
+        // Neighbors are yielded in the order they are visited in, even when not all of them are
+        // consumed, and with an iterator from an earlier call still alive.
+        let mut first = adapter.neighbors(&'a');
+        assert_eq!(first.next(), Some(('b', 1)));
+        assert_eq!(adapter.neighbors(&'d').collect::<Vec<_>>(), [('e', 3)]);
+        assert_eq!(first.next(), Some(('c', 4)));
+        drop(first);
+        assert!(adapter.buffer.borrow().is_empty());
+        assert!(adapter.buffer.borrow().capacity() >= capacity);
+        assert_eq!(adapter.neighbors(&'g').next(), None);
+    }
+
+    #[test]
+    fn searcher_over_graph_matches_dijkstra() {
+        let graph = TestGraph::new();
+        let mut searcher = Searcher::new(&graph, |_: &char| 0);
+        for starts in [vec!['a'], vec!['a', 'f'], vec!['b']] {
+            for goal in "abcdefg".chars() {
+                let goal = Goal::Node(goal);
+                let expected = dijkstra(&graph, starts.clone(), &goal);
+                let actual = searcher.find_path(starts.clone(), &goal);
+                assert_eq!(
+                    actual.as_ref().map(|path| path.cost),
+                    expected.as_ref().map(|path| path.cost)
+                );
+                if let Some(path) = actual {
+                    graph.check_edges(&path.nodes);
+                }
+            }
+        }
+    }
+}
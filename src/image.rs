@@ -0,0 +1,129 @@
+//! Exporting bitmaps to image files, for sharing visualizations outside the terminal.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use owo_colors::Rgb;
+
+use crate::bitmap::Bitmap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary PPM. Trivial to write, but large, and not every image viewer opens it.
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    /// Picks the format from the extension of the given path.
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => Ok(Self::Ppm),
+            Some("png") => Ok(Self::Png),
+            _ => bail!("cannot tell the image format of {path:?}. use a .ppm or .png extension"),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl<T> Bitmap<T> {
+    /// The RGB bytes of each element's color, row by row.
+    fn to_rgb(&self, color: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        self.elements
+            .iter()
+            .flat_map(|element| {
+                let Rgb(r, g, b) = color(element);
+                [r, g, b]
+            })
+            .collect()
+    }
+
+    /// Writes the bitmap as an image with one pixel per element, colored by `color`.
+    pub fn write_image(
+        &self,
+        mut out: impl Write,
+        format: ImageFormat,
+        color: impl Fn(&T) -> Rgb,
+    ) -> anyhow::Result<()> {
+        let rgb = self.to_rgb(color);
+        match format {
+            ImageFormat::Ppm => write_ppm(&mut out, self.width, self.height, &rgb)?,
+            ImageFormat::Png => {
+                let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                let mut writer = encoder.write_header()?;
+                writer.write_image_data(&rgb)?;
+                writer.finish()?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Saves the bitmap as an image file, in the format given by the path's extension.
+    pub fn save_image(&self, path: &Path, color: impl Fn(&T) -> Rgb) -> anyhow::Result<()> {
+        let format = ImageFormat::from_path(path)?;
+        let file = File::create(path).with_context(|| format!("cannot create {path:?}"))?;
+        self.write_image(BufWriter::new(file), format, color)
+            .with_context(|| format!("cannot write image {path:?}"))
+    }
+}
+
+fn write_ppm(out: &mut impl Write, width: u32, height: u32, rgb: &[u8]) -> io::Result<()> {
+    write!(out, "P6\n{width} {height}\n255\n")?;
+    out.write_all(rgb)
+}
+
+/// Saves numbered images of successive states of a bitmap into a directory, to be turned into an
+/// animation later.
+pub struct FrameWriter {
+    directory: PathBuf,
+    format: ImageFormat,
+    next_frame: usize,
+}
+
+impl FrameWriter {
+    /// Creates the directory if it does not exist yet. Existing frames in it are overwritten.
+    pub fn new(directory: impl Into<PathBuf>, format: ImageFormat) -> anyhow::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)
+            .with_context(|| format!("cannot create frame directory {directory:?}"))?;
+        Ok(Self {
+            directory,
+            format,
+            next_frame: 0,
+        })
+    }
+
+    /// The number of frames written so far.
+    pub fn frame_count(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Saves the bitmap as the next frame, and returns the path it was saved to.
+    pub fn write<T>(
+        &mut self,
+        bitmap: &Bitmap<T>,
+        color: impl Fn(&T) -> Rgb,
+    ) -> anyhow::Result<PathBuf> {
+        let path = self.directory.join(format!(
+            "frame-{:05}.{}",
+            self.next_frame,
+            self.format.extension()
+        ));
+        bitmap.save_image(&path, color)?;
+        self.next_frame += 1;
+        Ok(path)
+    }
+}
@@ -1,74 +1,480 @@
+pub mod answers;
 pub mod astar;
 pub mod bitmap;
+pub mod bitmap_search;
+pub mod debug;
+pub mod graph;
+pub mod image;
 pub mod math;
+pub mod registry;
+pub mod report;
+pub mod sparse_bitmap;
+pub mod testing;
+pub mod timing;
 
 pub use anyhow;
 pub use log;
 pub use owo_colors;
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
+};
 
+use answers::{Answers, Mismatch};
 use anyhow::Context;
-use clap::Parser;
-use log::{error, info, LevelFilter};
+use clap::{
+    builder::{PossibleValue, PossibleValuesParser},
+    CommandFactory, FromArgMatches, Parser,
+};
+use debug::{DebugFlag, DebugFlagInfo};
+use log::{error, info, warn, LevelFilter};
+use registry::Day;
+use report::{OutputFormat, Record};
+use timing::{BenchReport, Timings};
 
-#[derive(Parser)]
-struct ChallengeArgs {
-    input_files: Vec<PathBuf>,
+#[derive(Parser, Clone, Default)]
+pub struct ChallengeArgs {
+    /// Input files to solve. `-` reads the input from stdin.
+    pub input_files: Vec<PathBuf>,
+    /// Solve the given string as if it were the contents of an input file. Can be passed multiple
+    /// times.
     #[clap(long)]
-    debug: Vec<String>,
+    pub input_str: Vec<String>,
+    /// Enable a debug flag. Can be passed multiple times.
+    #[clap(long)]
+    pub debug: Vec<String>,
+    /// Compare answers against the ones in `<input file>.answers` and fail if they don't match.
+    #[clap(long)]
+    pub check: bool,
+    /// Solve each input file this many times and report timing statistics.
+    #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub bench: Option<u32>,
+    /// Keep solving the remaining inputs after one fails, and summarize the failures at the end.
+    #[clap(long)]
+    pub keep_going: bool,
+    /// Solve up to this many inputs in parallel. Note that timings get less accurate the more
+    /// threads compete for the CPU.
+    #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: Option<u32>,
+    /// Format to write the results in. Answers are only printed as they are found in the `human`
+    /// format; the others print all results at the end.
+    #[clap(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+}
+
+impl ChallengeArgs {
+    /// Parses the command line arguments, only accepting the given debug flags for `--debug`.
+    pub fn parse_with_debug_flags(debug_flags: &[DebugFlagInfo]) -> Self {
+        let possible_values = debug_flags
+            .iter()
+            .map(|flag| PossibleValue::new(flag.name).help(flag.description.trim()));
+        let matches = Self::command()
+            .mut_arg("debug", |arg| {
+                arg.value_parser(PossibleValuesParser::new(possible_values))
+            })
+            .get_matches();
+        Self::from_arg_matches(&matches).unwrap_or_else(|error| error.exit())
+    }
 }
 
+#[derive(Clone)]
 pub struct Challenge {
     pub input: String,
-    pub debug_flags: HashSet<String>,
+    debug_flags: HashSet<String>,
+}
+
+impl Challenge {
+    /// Whether the debug flag was passed on the command line.
+    pub fn debug(&self, flag: impl DebugFlag) -> bool {
+        self.debug_flags.contains(flag.name())
+    }
+}
+
+/// Where the input of a challenge came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// The Nth `--input-str` argument, counting from 1.
+    Inline(usize),
+}
+
+impl InputSource {
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            InputSource::File(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.to_string_lossy()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Inline(n) => write!(f, "<input-str #{n}>"),
+        }
+    }
 }
 
 struct LoadedChallenge {
-    filename: PathBuf,
-    inner: Challenge,
+    source: InputSource,
+    /// The error, if the input could not be read.
+    inner: anyhow::Result<Challenge>,
 }
 
-fn load_challenges() -> anyhow::Result<Vec<LoadedChallenge>> {
-    let args = ChallengeArgs::parse();
-    let mut challenges = vec![];
+/// Reads all the inputs. An input that cannot be read does not stop the others from being read;
+/// it fails on its own once it's its turn to be solved.
+fn load_challenges(args: &ChallengeArgs) -> Vec<LoadedChallenge> {
+    let mut inputs = vec![];
+    for filename in &args.input_files {
+        if filename.as_os_str() == "-" {
+            let read_before = inputs
+                .iter()
+                .any(|(source, _)| *source == InputSource::Stdin);
+            let input = if read_before {
+                Err(anyhow::anyhow!("stdin can only be read once"))
+            } else {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("read input from stdin")
+                    .map(|_| input)
+            };
+            inputs.push((InputSource::Stdin, input));
+        } else {
+            let input = std::fs::read_to_string(filename)
+                .with_context(|| format!("read input file {filename:?}"));
+            inputs.push((InputSource::File(filename.clone()), input));
+        }
+    }
+    for (i, input) in args.input_str.iter().enumerate() {
+        inputs.push((InputSource::Inline(i + 1), Ok(input.clone())));
+    }
+
     let debug_flags: HashSet<String> = args.debug.iter().cloned().collect();
-    for filename in args.input_files {
-        let input = std::fs::read_to_string(&filename)
-            .context("read input file")?
-            .replace("\r\n", "\n");
-        challenges.push(LoadedChallenge {
-            filename,
-            inner: Challenge {
-                input,
+    inputs
+        .into_iter()
+        .map(|(source, input)| LoadedChallenge {
+            source,
+            inner: input.map(|input| Challenge {
+                input: input.replace("\r\n", "\n"),
                 debug_flags: debug_flags.clone(),
-            },
-        });
+            }),
+        })
+        .collect()
+}
+
+fn failure_context(index: usize, source: &InputSource) -> String {
+    format!("file #{} {source} failed", index + 1)
+}
+
+/// The answers a solver produced for a single input.
+pub struct ChallengeResult {
+    pub source: InputSource,
+    /// The error, if the solver failed.
+    pub answers: anyhow::Result<Answers>,
+    /// Answers that did not match the expected ones. `None` if the answers were not checked.
+    pub mismatches: Option<Vec<Mismatch>>,
+    /// Timings of the last run.
+    pub timings: Timings,
+    /// `None` if not benchmarking.
+    pub bench: Option<BenchReport>,
+}
+
+impl ChallengeResult {
+    pub fn failed(&self) -> bool {
+        self.answers.is_err()
+    }
+
+    pub fn has_mismatches(&self) -> bool {
+        self.mismatches
+            .as_ref()
+            .is_some_and(|mismatches| !mismatches.is_empty())
+    }
+}
+
+/// The outcome of solving a single input, before it is reported.
+struct Solved {
+    /// The answers and the expected answers, if checking.
+    answers: anyhow::Result<(Answers, Option<Answers>)>,
+    /// Timings of each run.
+    timings: Vec<Timings>,
+}
+
+fn solve(
+    args: &ChallengeArgs,
+    index: usize,
+    source: &InputSource,
+    challenge: &Challenge,
+    f: impl Fn(Challenge) -> anyhow::Result<Answers>,
+) -> Solved {
+    info!("file #{}: {source}", index + 1);
+    let context = || failure_context(index, source);
+    let runs = args.bench.unwrap_or(1);
+    let mut timings = vec![];
+    let mut answers = Ok(Answers::new());
+    for _ in 0..runs {
+        let start = Instant::now();
+        answers = f(challenge.clone()).with_context(context);
+        let total = start.elapsed();
+        match &answers {
+            Ok(answers) => timings.push(Timings {
+                parse: answers.parse_time(),
+                parts: answers.part_times().clone(),
+                total,
+            }),
+            Err(_) => {
+                timings.push(Timings {
+                    total,
+                    ..Default::default()
+                });
+                break;
+            }
+        }
+    }
+
+    // A broken answers file fails the input just like the solver failing would.
+    let expected = match source.path() {
+        Some(path) if args.check => Answers::load_expected(path).with_context(context),
+        _ => Ok(None),
+    };
+    Solved {
+        answers: answers.and_then(|answers| Ok((answers, expected?))),
+        timings,
     }
-    Ok(challenges)
 }
 
-fn run_challenges(mut f: impl FnMut(Challenge) -> anyhow::Result<()>) -> anyhow::Result<()> {
-    let challenges = load_challenges().context("cannot load challenges")?;
-    for (i, challenge) in challenges.into_iter().enumerate() {
-        info!("file #{}: {}", i + 1, challenge.filename.to_string_lossy());
-        f(challenge.inner)
-            .with_context(|| format!("file #{} {:?} failed", i + 1, challenge.filename))?;
+/// Prints the answers to an input and turns them into a result.
+fn emit(
+    args: &ChallengeArgs,
+    index: usize,
+    source: InputSource,
+    solved: Solved,
+) -> ChallengeResult {
+    let human = args.output == OutputFormat::Human;
+    let timings = solved.timings.last().cloned().unwrap_or_default();
+    let (answers, expected) = match solved.answers {
+        Ok(result) => result,
+        Err(error) => {
+            error!("{error:?}");
+            return ChallengeResult {
+                source,
+                answers: Err(error),
+                mismatches: None,
+                timings,
+                bench: None,
+            };
+        }
+    };
+    let bench = args
+        .bench
+        .and_then(|_| BenchReport::from_runs(&solved.timings));
+
+    if args.check {
+        match source.path() {
+            Some(path) if expected.is_none() => warn!(
+                "file #{}: no expected answers found in {:?}",
+                index + 1,
+                Answers::expected_answers_path(path)
+            ),
+            None => warn!(
+                "file #{}: answers for {source} cannot be checked",
+                index + 1
+            ),
+            _ => (),
+        }
+    }
+    let mismatches = match &expected {
+        Some(expected) => {
+            if human {
+                print!("{}", answers.display_checked(expected));
+            }
+            Some(answers.check(expected))
+        }
+        None => {
+            if human {
+                print!("{answers}");
+            }
+            None
+        }
+    };
+
+    info!("file #{}: {timings}", index + 1);
+    if let Some(bench) = bench.as_ref().filter(|_| human) {
+        print!("{bench}");
+    }
+
+    ChallengeResult {
+        source,
+        answers: Ok(answers),
+        mismatches,
+        timings,
+        bench,
     }
-    Ok(())
 }
 
-pub fn wrap_main(f: impl FnMut(Challenge) -> anyhow::Result<()>) {
+/// Solves each input with `f`. Stops at the first input that cannot be read or that the solver
+/// fails on, unless `--keep-going` was passed; failures are returned as part of the results.
+///
+/// With `--jobs`, inputs are solved on multiple threads. Answers are still printed and returned in
+/// the order the inputs were given in, as soon as all the inputs before them are done.
+pub fn run_challenges(
+    args: &ChallengeArgs,
+    f: impl Fn(Challenge) -> anyhow::Result<Answers> + Sync,
+) -> Vec<ChallengeResult> {
+    let mut sources = vec![];
+    let mut challenges = vec![];
+    let mut load_errors = vec![];
+    for (index, loaded) in load_challenges(args).into_iter().enumerate() {
+        match loaded.inner {
+            Ok(challenge) => {
+                challenges.push(Some(challenge));
+                load_errors.push(None);
+            }
+            Err(error) => {
+                challenges.push(None);
+                load_errors.push(Some(error.context(failure_context(index, &loaded.source))));
+            }
+        }
+        sources.push(loaded.source);
+    }
+    let jobs = (args.jobs.unwrap_or(1) as usize).min(challenges.len());
+    let next = AtomicUsize::new(0);
+    // Inputs past the first failure do not need to be solved, unless we're keeping going.
+    let first_failure = AtomicUsize::new(usize::MAX);
+
+    let mut results = vec![];
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (sources, challenges, next, first_failure, f) =
+                (&sources, &challenges, &next, &first_failure, &f);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= challenges.len()
+                    || (!args.keep_going && index > first_failure.load(Ordering::Relaxed))
+                {
+                    break;
+                }
+                // Inputs that could not be read are sent as `None`, and failed on the main
+                // thread, which owns their errors.
+                let solved = challenges[index]
+                    .as_ref()
+                    .map(|challenge| solve(args, index, &sources[index], challenge, f));
+                if solved.as_ref().is_none_or(|solved| solved.answers.is_err()) {
+                    first_failure.fetch_min(index, Ordering::Relaxed);
+                }
+                if sender.send((index, solved)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        for (index, solved) in receiver {
+            pending.insert(index, solved);
+            while let Some(solved) = pending.remove(&results.len()) {
+                let index = results.len();
+                let solved = solved.unwrap_or_else(|| Solved {
+                    answers: Err(load_errors[index].take().expect("input emitted twice")),
+                    timings: vec![],
+                });
+                let result = emit(args, index, sources[index].clone(), solved);
+                let failed = result.failed();
+                results.push(result);
+                if failed && !args.keep_going {
+                    return;
+                }
+            }
+        }
+    });
+    results
+}
+
+/// Writes the results to stdout in the format chosen on the command line.
+pub fn write_report(format: OutputFormat, records: &[Record]) {
+    if let Err(error) = report::write(&mut std::io::stdout().lock(), format, records) {
+        error!("cannot write report: {error}");
+    }
+}
+
+/// Logs how many inputs passed, along with the reason each of the others failed.
+pub fn log_summary<'a>(results: impl IntoIterator<Item = &'a ChallengeResult>) {
+    let mut passed = 0;
+    let mut failed = 0;
+    for result in results {
+        if let Err(error) = &result.answers {
+            error!("{error:#}");
+            failed += 1;
+        } else if let Some(mismatches) = result.mismatches.as_ref().filter(|m| !m.is_empty()) {
+            let parts: Vec<_> = mismatches.iter().map(|m| m.part.to_string()).collect();
+            error!(
+                "{}: answers to part {} did not match the expected ones",
+                result.source,
+                parts.join(", ")
+            );
+            failed += 1;
+        } else {
+            passed += 1;
+        }
+    }
+    info!("{passed} passed, {failed} failed");
+}
+
+pub fn init_logger() {
     env_logger::builder()
         .format_timestamp(None)
         .filter_module("aoc", LevelFilter::Debug)
+        // Filters match module path prefixes, so this covers all the `day_N` crates.
+        .filter_module("day_", LevelFilter::Info)
         .init();
+}
 
-    match run_challenges(f) {
-        Ok(()) => (),
-        Err(error) => {
-            error!("{error:?}");
+pub fn wrap_main(day: Day) {
+    init_logger();
+
+    let args = ChallengeArgs::parse_with_debug_flags(day.debug_flags);
+    let results = run_challenges(&args, day.solver);
+    let records: Vec<_> = results
+        .iter()
+        .map(|result| Record::from_result(None, result))
+        .collect();
+    write_report(args.output, &records);
+
+    if args.keep_going {
+        log_summary(&results);
+        if results
+            .iter()
+            .any(|result| result.failed() || result.has_mismatches())
+        {
             std::process::exit(1);
         }
     }
+    if results.iter().any(|result| result.failed()) {
+        std::process::exit(1);
+    }
+    let mismatched = results
+        .iter()
+        .filter(|result| result.has_mismatches())
+        .count();
+    if mismatched > 0 {
+        error!(
+            "{mismatched} of {} files did not match the expected answers",
+            results.len()
+        );
+        std::process::exit(1);
+    }
 }
@@ -3,3 +3,43 @@ pub struct Size<T> {
     pub width: T,
     pub height: T,
 }
+
+/// An inclusive, axis-aligned rectangle of grid positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: (i32, i32),
+    pub max: (i32, i32),
+}
+
+impl BoundingBox {
+    /// The box containing just the given position.
+    pub fn at(position: (i32, i32)) -> Self {
+        Self {
+            min: position,
+            max: position,
+        }
+    }
+
+    /// Grows the box so that it contains the given position.
+    pub fn extend(&mut self, (x, y): (i32, i32)) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    pub fn contains(&self, (x, y): (i32, i32)) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    pub fn size(&self) -> Size<u32> {
+        Size {
+            width: self.max.0.abs_diff(self.min.0) + 1,
+            height: self.max.1.abs_diff(self.min.1) + 1,
+        }
+    }
+
+    /// All positions inside the box, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (i32, i32)> {
+        let &BoundingBox { min, max } = self;
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+    }
+}
@@ -0,0 +1,166 @@
+//! Registry of days, used by the multi-day runner.
+
+use std::{collections::BTreeMap, ops::RangeInclusive, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::{answers::Answers, debug::DebugFlagInfo, Challenge};
+
+pub type Solver = fn(Challenge) -> anyhow::Result<Answers>;
+
+/// A single day's solver, as exported by each day crate.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub solver: Solver,
+    /// The debug flags the solver understands, usually `Flag::ALL` from
+    /// [`debug_flags!`][crate::debug_flags].
+    pub debug_flags: &'static [DebugFlagInfo],
+}
+
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<u32, Day>,
+}
+
+impl Registry {
+    pub fn register(&mut self, day: Day) {
+        let previous = self.days.insert(day.number, day);
+        assert!(
+            previous.is_none(),
+            "day {} was registered more than once",
+            day.number
+        );
+    }
+
+    pub fn get(&self, number: u32) -> Option<&Day> {
+        self.days.get(&number)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = &Day> {
+        self.days.values()
+    }
+
+    /// Returns all registered days matching the selection, in ascending order.
+    pub fn select(&self, selection: &DaySelection) -> anyhow::Result<Vec<&Day>> {
+        match selection {
+            DaySelection::All => Ok(self.days().collect()),
+            DaySelection::Range(range) => {
+                if range.start() == range.end() {
+                    let day = self
+                        .get(*range.start())
+                        .ok_or_else(|| anyhow!("day {} is not registered", range.start()))?;
+                    Ok(vec![day])
+                } else {
+                    Ok(self.days.range(range.clone()).map(|(_, day)| day).collect())
+                }
+            }
+        }
+    }
+}
+
+/// Which days to run, as passed on the command line: `7`, `1..=14`, `1..15`, or `all`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    Range(RangeInclusive<u32>),
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |s: &str| s.trim().parse::<u32>().context("invalid day number");
+        if s == "all" {
+            Ok(DaySelection::All)
+        } else if let Some((start, end)) = s.split_once("..=") {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+            if end < start {
+                bail!("day range {s} is empty");
+            }
+            Ok(DaySelection::Range(start..=end))
+        } else if let Some((start, end)) = s.split_once("..") {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+            if end <= start {
+                bail!("day range {s} is empty");
+            }
+            Ok(DaySelection::Range(start..=end - 1))
+        } else {
+            let day = parse_day(s)?;
+            Ok(DaySelection::Range(day..=day))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> anyhow::Result<DaySelection> {
+        s.parse()
+    }
+
+    #[test]
+    fn parses_selections() {
+        assert_eq!(parse("7").unwrap(), DaySelection::Range(7..=7));
+        assert_eq!(parse("1..=14").unwrap(), DaySelection::Range(1..=14));
+        assert_eq!(parse("1..15").unwrap(), DaySelection::Range(1..=14));
+        assert_eq!(parse("3..4").unwrap(), DaySelection::Range(3..=3));
+        assert_eq!(parse("all").unwrap(), DaySelection::All);
+    }
+
+    #[test]
+    fn rejects_empty_ranges() {
+        assert!(parse("5..=1").is_err());
+        assert!(parse("3..3").is_err());
+        assert!(parse("5..1").is_err());
+        // A range ending at day 0 must not underflow.
+        assert!(parse("0..0").is_err());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for s in ["", "seven", "-1", "1..", "..=5", "1...5", "1..=x", "All"] {
+            assert!(parse(s).is_err(), "{s:?} should not parse");
+        }
+    }
+
+    fn registry(numbers: &[u32]) -> Registry {
+        fn solver(_: Challenge) -> anyhow::Result<Answers> {
+            unreachable!()
+        }
+
+        let mut registry = Registry::default();
+        for &number in numbers {
+            registry.register(Day {
+                number,
+                solver,
+                debug_flags: &[],
+            });
+        }
+        registry
+    }
+
+    fn selected(registry: &Registry, selection: &str) -> anyhow::Result<Vec<u32>> {
+        let days = registry.select(&parse(selection)?)?;
+        Ok(days.into_iter().map(|day| day.number).collect())
+    }
+
+    #[test]
+    fn selects_registered_days() {
+        let registry = registry(&[3, 1, 14, 2]);
+        assert_eq!(selected(&registry, "all").unwrap(), [1, 2, 3, 14]);
+        assert_eq!(selected(&registry, "2").unwrap(), [2]);
+        assert_eq!(selected(&registry, "2..=14").unwrap(), [2, 3, 14]);
+        assert_eq!(selected(&registry, "2..14").unwrap(), [2, 3]);
+        // Ranges skip over days that are not registered.
+        assert_eq!(selected(&registry, "4..=13").unwrap(), [] as [u32; 0]);
+    }
+
+    #[test]
+    fn single_unregistered_day_is_an_error() {
+        let registry = registry(&[1, 2]);
+        let error = selected(&registry, "13").unwrap_err();
+        assert_eq!(error.to_string(), "day 13 is not registered");
+    }
+}
@@ -0,0 +1,237 @@
+//! Machine-readable reports of run results, for aggregating them with other tools.
+
+use std::{io, time::Duration};
+
+use clap::ValueEnum;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    answers::{Answer, Answers, Mismatch},
+    timing::{BenchReport, Statistics, Timings},
+    ChallengeResult,
+};
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Answers as text, meant to be read by a human.
+    #[default]
+    Human,
+    /// A JSON array with one object per input.
+    Json,
+    /// A CSV table with one row per input.
+    Csv,
+}
+
+/// The outcome of solving a single input, or of failing to load a day's inputs.
+#[derive(Debug, Clone, Default)]
+pub struct Record {
+    /// `None` when running a single day's binary.
+    pub day: Option<u32>,
+    /// `None` if the inputs could not be loaded.
+    pub input: Option<String>,
+    pub answers: Option<Answers>,
+    pub mismatches: Option<Vec<Mismatch>>,
+    pub timings: Option<Timings>,
+    pub bench: Option<BenchReport>,
+    /// The error and its context chain, outermost first. Empty if there was no error.
+    pub error: Vec<String>,
+}
+
+impl Record {
+    pub fn from_result(day: Option<u32>, result: &ChallengeResult) -> Self {
+        let (answers, error) = match &result.answers {
+            Ok(answers) => (Some(answers.clone()), vec![]),
+            Err(error) => (None, error_chain(error)),
+        };
+        Self {
+            day,
+            input: Some(result.source.to_string()),
+            answers,
+            mismatches: result.mismatches.clone(),
+            timings: Some(result.timings.clone()),
+            bench: result.bench.clone(),
+            error,
+        }
+    }
+
+    pub fn from_error(day: Option<u32>, error: &anyhow::Error) -> Self {
+        Self {
+            day,
+            error: error_chain(error),
+            ..Default::default()
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        if !self.error.is_empty() {
+            "failed"
+        } else if self
+            .mismatches
+            .as_ref()
+            .is_some_and(|mismatches| !mismatches.is_empty())
+        {
+            "mismatch"
+        } else {
+            "ok"
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        if let Some(day) = self.day {
+            object.insert("day".into(), day.into());
+        }
+        object.insert("input".into(), self.input.clone().into());
+        object.insert("status".into(), self.status().into());
+        object.insert(
+            "answers".into(),
+            self.answers.as_ref().map(answers_to_json).into(),
+        );
+        if let Some(mismatches) = &self.mismatches {
+            let mismatches = mismatches
+                .iter()
+                .map(|mismatch| {
+                    json!({
+                        "part": mismatch.part,
+                        "expected": answer_to_json(&mismatch.expected),
+                        "actual": mismatch.actual.as_ref().map(answer_to_json),
+                    })
+                })
+                .collect::<Vec<_>>();
+            object.insert("mismatches".into(), mismatches.into());
+        }
+        object.insert(
+            "timings".into(),
+            self.timings.as_ref().map(timings_to_json).into(),
+        );
+        if let Some(bench) = &self.bench {
+            object.insert("bench".into(), bench_to_json(bench));
+        }
+        object.insert("error".into(), self.error.clone().into());
+        Value::Object(object)
+    }
+
+    fn to_csv_row(&self) -> [String; 10] {
+        let part = |part| {
+            self.answers
+                .as_ref()
+                .and_then(|answers| answers.get(part))
+                .map(|answer| answer.to_string())
+                .unwrap_or_default()
+        };
+        let nanos = |duration: Option<Duration>| {
+            duration
+                .map(|duration| duration.as_nanos().to_string())
+                .unwrap_or_default()
+        };
+        let timings = self.timings.as_ref();
+        [
+            self.day.map(|day| day.to_string()).unwrap_or_default(),
+            self.input.clone().unwrap_or_default(),
+            self.status().to_owned(),
+            part(1),
+            part(2),
+            nanos(timings.and_then(|timings| timings.parse)),
+            nanos(timings.and_then(|timings| timings.parts.get(&1).copied())),
+            nanos(timings.and_then(|timings| timings.parts.get(&2).copied())),
+            nanos(timings.map(|timings| timings.total)),
+            self.error.join(": "),
+        ]
+    }
+}
+
+fn error_chain(error: &anyhow::Error) -> Vec<String> {
+    error.chain().map(|cause| cause.to_string()).collect()
+}
+
+fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(x) => (*x).into(),
+        Answer::String(s) => s.clone().into(),
+        Answer::Grid(lines) => lines.clone().into(),
+    }
+}
+
+fn answers_to_json(answers: &Answers) -> Value {
+    Value::Object(
+        answers
+            .iter()
+            .map(|(part, answer)| (part.to_string(), answer_to_json(answer)))
+            .collect(),
+    )
+}
+
+fn nanos_to_json(duration: Duration) -> Value {
+    // Durations in nanoseconds only overflow a u64 after ~584 years.
+    (duration.as_nanos() as u64).into()
+}
+
+fn timings_to_json(timings: &Timings) -> Value {
+    json!({
+        "parse_ns": timings.parse.map(nanos_to_json),
+        "parts_ns": timings
+            .parts
+            .iter()
+            .map(|(part, &duration)| (part.to_string(), nanos_to_json(duration)))
+            .collect::<Map<_, _>>(),
+        "total_ns": nanos_to_json(timings.total),
+    })
+}
+
+fn bench_to_json(bench: &BenchReport) -> Value {
+    let statistics = |stats: &Statistics| {
+        json!({
+            "min_ns": nanos_to_json(stats.min),
+            "median_ns": nanos_to_json(stats.median),
+            "mean_ns": nanos_to_json(stats.mean),
+            "stddev_ns": nanos_to_json(stats.stddev),
+        })
+    };
+    json!({
+        "runs": bench.runs,
+        "parse": bench.parse.as_ref().map(statistics),
+        "parts": bench
+            .parts
+            .iter()
+            .map(|(part, stats)| (part.to_string(), statistics(stats)))
+            .collect::<Map<_, _>>(),
+        "total": statistics(&bench.total),
+    })
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Writes the records in the given format. Writes nothing for [`OutputFormat::Human`], since
+/// human-readable output is printed while solving.
+pub fn write(out: &mut impl io::Write, format: OutputFormat, records: &[Record]) -> io::Result<()> {
+    match format {
+        OutputFormat::Human => (),
+        OutputFormat::Json => {
+            let records: Vec<_> = records.iter().map(Record::to_json).collect();
+            serde_json::to_writer_pretty(&mut *out, &records)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            writeln!(
+                out,
+                "day,input,status,part_1,part_2,parse_ns,part_1_ns,part_2_ns,total_ns,error"
+            )?;
+            for record in records {
+                let row: Vec<_> = record
+                    .to_csv_row()
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+    }
+    Ok(())
+}
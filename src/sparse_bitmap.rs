@@ -0,0 +1,232 @@
+//! Unbounded bitmaps, for when the area a puzzle covers is not known up front.
+
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use crate::{bitmap::Bitmap, math::BoundingBox};
+
+/// A bitmap that grows in all directions as elements are written to it, including into negative
+/// coordinates. Positions that were never written to hold the `blank` element.
+#[derive(Debug, Clone)]
+pub struct SparseBitmap<T> {
+    elements: HashMap<(i32, i32), T>,
+    bounds: Option<BoundingBox>,
+    pub blank: T,
+}
+
+impl<T> SparseBitmap<T> {
+    pub fn new(blank: T) -> Self {
+        Self {
+            elements: HashMap::new(),
+            bounds: None,
+            blank,
+        }
+    }
+
+    /// The smallest box containing every position written to so far, or `None` if nothing was
+    /// written yet. Removing elements does not shrink it.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    /// The number of positions written to.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// The element at the given position, or `None` if it was never written to.
+    pub fn get(&self, position: (i32, i32)) -> Option<&T> {
+        self.elements.get(&position)
+    }
+
+    pub fn set(&mut self, position: (i32, i32), value: T) {
+        self.extend_bounds(position);
+        self.elements.insert(position, value);
+    }
+
+    /// Removes the element at the given position, making it blank again.
+    pub fn remove(&mut self, position: (i32, i32)) -> Option<T> {
+        self.elements.remove(&position)
+    }
+
+    /// Iterates over the positions written to, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((i32, i32), &T)> {
+        self.elements
+            .iter()
+            .map(|(&position, element)| (position, element))
+    }
+
+    fn extend_bounds(&mut self, position: (i32, i32)) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(position),
+            None => self.bounds = Some(BoundingBox::at(position)),
+        }
+    }
+}
+
+impl<T> SparseBitmap<T>
+where
+    T: Clone,
+{
+    /// Modifies the element at the given position in place, starting from a blank one if it was
+    /// never written to.
+    pub fn update<R>(&mut self, position: (i32, i32), f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self[position])
+    }
+
+    /// Copies the part of the bitmap inside `bounds` into a dense [`Bitmap`], whose `(0, 0)` is
+    /// `bounds.min`.
+    pub fn to_bitmap_within(&self, bounds: BoundingBox) -> Bitmap<T> {
+        let size = bounds.size();
+        let mut bitmap = Bitmap::new(size.width, size.height, self.blank.clone());
+        for (&(x, y), element) in &self.elements {
+            if bounds.contains((x, y)) {
+                bitmap[(x - bounds.min.0, y - bounds.min.1)] = element.clone();
+            }
+        }
+        bitmap
+    }
+
+    /// Copies the bitmap into a dense [`Bitmap`] covering its [`bounds`][Self::bounds]. Also
+    /// returns the position the dense bitmap's `(0, 0)` corresponds to.
+    pub fn to_bitmap(&self) -> (Bitmap<T>, (i32, i32)) {
+        match self.bounds {
+            Some(bounds) => (self.to_bitmap_within(bounds), bounds.min),
+            None => (Bitmap::new(0, 0, self.blank.clone()), (0, 0)),
+        }
+    }
+}
+
+impl<T> Index<(i32, i32)> for SparseBitmap<T> {
+    type Output = T;
+
+    fn index(&self, index: (i32, i32)) -> &Self::Output {
+        self.get(index).unwrap_or(&self.blank)
+    }
+}
+
+/// Indexing mutably writes a blank element at the position if there was none yet.
+impl<T> IndexMut<(i32, i32)> for SparseBitmap<T>
+where
+    T: Clone,
+{
+    fn index_mut(&mut self, index: (i32, i32)) -> &mut Self::Output {
+        self.extend_bounds(index);
+        self.elements
+            .entry(index)
+            .or_insert_with(|| self.blank.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(min: (i32, i32), max: (i32, i32)) -> BoundingBox {
+        BoundingBox { min, max }
+    }
+
+    /// The rows of a dense bitmap of characters, for comparing against.
+    fn rows(bitmap: &Bitmap<char>) -> Vec<String> {
+        bitmap
+            .elements
+            .chunks(bitmap.width as usize)
+            .map(|row| row.iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let mut sparse = SparseBitmap::new('.');
+        sparse.set((-3, -2), 'a');
+        sparse.set((-1, 1), 'b');
+        assert_eq!(sparse[(-3, -2)], 'a');
+        assert_eq!(sparse[(-1, 1)], 'b');
+        assert_eq!(sparse[(-2, -2)], '.');
+        assert_eq!(sparse.get((-2, -2)), None);
+        assert_eq!(sparse.bounds(), Some(bounds((-3, -2), (-1, 1))));
+        assert_eq!(sparse.len(), 2);
+    }
+
+    #[test]
+    fn set_grows_bounds() {
+        let mut sparse = SparseBitmap::new('.');
+        assert_eq!(sparse.bounds(), None);
+        sparse.set((2, 3), 'a');
+        assert_eq!(sparse.bounds(), Some(bounds((2, 3), (2, 3))));
+        sparse.set((5, 1), 'b');
+        assert_eq!(sparse.bounds(), Some(bounds((2, 1), (5, 3))));
+        sparse.set((3, 2), 'c');
+        assert_eq!(sparse.bounds(), Some(bounds((2, 1), (5, 3))));
+    }
+
+    #[test]
+    fn index_mut_grows_bounds() {
+        let mut sparse = SparseBitmap::new('.');
+        sparse[(1, 1)] = 'a';
+        // Only looking at an element through `IndexMut` writes it too.
+        let _ = &mut sparse[(-4, 6)];
+        assert_eq!(sparse.bounds(), Some(bounds((-4, 1), (1, 6))));
+        assert_eq!(sparse.get((-4, 6)), Some(&'.'));
+        assert_eq!(sparse.len(), 2);
+
+        sparse.update((7, 0), |element| *element = 'b');
+        assert_eq!(sparse.bounds(), Some(bounds((-4, 0), (7, 6))));
+        assert_eq!(sparse[(7, 0)], 'b');
+    }
+
+    #[test]
+    fn remove_does_not_shrink_bounds() {
+        let mut sparse = SparseBitmap::new('.');
+        sparse.set((0, 0), 'a');
+        sparse.set((4, 4), 'b');
+        assert_eq!(sparse.remove((4, 4)), Some('b'));
+        assert_eq!(sparse.remove((4, 4)), None);
+        assert_eq!(sparse[(4, 4)], '.');
+        assert_eq!(sparse.bounds(), Some(bounds((0, 0), (4, 4))));
+        assert_eq!(sparse.len(), 1);
+    }
+
+    #[test]
+    fn to_bitmap_is_offset_by_origin() {
+        let mut sparse = SparseBitmap::new('.');
+        sparse.set((-2, -1), 'a');
+        sparse.set((1, 0), 'b');
+        sparse.set((0, 1), 'c');
+
+        let (bitmap, origin) = sparse.to_bitmap();
+        assert_eq!(origin, (-2, -1));
+        assert_eq!(rows(&bitmap), ["a...", "...b", "..c."]);
+        for ((x, y), &element) in sparse.iter() {
+            assert_eq!(bitmap[(x - origin.0, y - origin.1)], element);
+        }
+        // Positions outside the dense bitmap read as blank.
+        assert_eq!(bitmap[(-1, 0)], '.');
+    }
+
+    #[test]
+    fn to_bitmap_within_crops_and_pads() {
+        let mut sparse = SparseBitmap::new('.');
+        sparse.set((-2, -1), 'a');
+        sparse.set((1, 0), 'b');
+        sparse.set((0, 1), 'c');
+
+        let bitmap = sparse.to_bitmap_within(bounds((0, -1), (2, 2)));
+        assert_eq!(rows(&bitmap), ["...", ".b.", "c..", "..."]);
+        let bitmap = sparse.to_bitmap_within(bounds((-2, -1), (-2, -1)));
+        assert_eq!(rows(&bitmap), ["a"]);
+    }
+
+    #[test]
+    fn empty_to_bitmap() {
+        let sparse = SparseBitmap::new('.');
+        let (bitmap, origin) = sparse.to_bitmap();
+        assert_eq!((bitmap.width, bitmap.height, origin), (0, 0, (0, 0)));
+    }
+}
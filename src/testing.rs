@@ -0,0 +1,92 @@
+//! Support for testing solvers against the puzzles' examples.
+
+use std::collections::HashSet;
+
+use crate::{
+    answers::{Answer, Answers},
+    Challenge,
+};
+
+/// Runs the solver on the given input and panics if any of the expected answers does not match.
+#[track_caller]
+pub fn check_example(
+    solver: impl FnOnce(Challenge) -> anyhow::Result<Answers>,
+    input: &str,
+    expected: &[(u32, Answer)],
+) {
+    let challenge = Challenge {
+        input: input.replace("\r\n", "\n"),
+        debug_flags: HashSet::new(),
+    };
+    let answers = match solver(challenge) {
+        Ok(answers) => answers,
+        Err(error) => panic!("solver failed: {error:?}"),
+    };
+
+    let mut expected_answers = Answers::new();
+    for (part, answer) in expected {
+        expected_answers.set(*part, answer.clone());
+    }
+    let mismatches = answers.check(&expected_answers);
+    if !mismatches.is_empty() {
+        let mut message = String::from("answers do not match the expected ones:");
+        for mismatch in mismatches {
+            let actual = mismatch
+                .actual
+                .map(|answer| answer.to_string())
+                .unwrap_or_else(|| "(missing)".to_owned());
+            message += &format!(
+                "\npart {}:\n  expected: {}\n  actual: {actual}",
+                mismatch.part, mismatch.expected
+            );
+        }
+        panic!("{message}");
+    }
+}
+
+/// Parses the part number out of a `partN` identifier, as used by [`example_test!`].
+pub fn part_number(ident: &str) -> u32 {
+    ident
+        .strip_prefix("part")
+        .and_then(|number| number.parse().ok())
+        .unwrap_or_else(|| panic!("expected partN, got {ident}"))
+}
+
+/// Generates a test running a solver on an example input file and checking its answers.
+///
+/// The path to the example is relative to the day crate's directory. The test is named `example`,
+/// unless a different name is given before the solver.
+///
+/// ```ignore
+/// aoc::example_test!(challenge_main, "example.txt", part1 = 13, part2 = 36);
+/// aoc::example_test!(larger_example: challenge_main, "larger_example.txt", part2 = 36);
+/// ```
+#[macro_export]
+macro_rules! example_test {
+    (
+        $(#[$attr:meta])*
+        $name:ident : $solver:path, $file:literal $(, $part:ident = $answer:expr)* $(,)?
+    ) => {
+        #[test]
+        $(#[$attr])*
+        fn $name() {
+            $crate::testing::check_example(
+                $solver,
+                include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $file)),
+                &[$((
+                    $crate::testing::part_number(stringify!($part)),
+                    $crate::answers::Answer::from($answer),
+                )),*],
+            );
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        $solver:path, $file:literal $(, $part:ident = $answer:expr)* $(,)?
+    ) => {
+        $crate::example_test!(
+            $(#[$attr])*
+            example: $solver, $file $(, $part = $answer)*
+        );
+    };
+}
@@ -0,0 +1,129 @@
+//! Timing and benchmarking of solvers.
+
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+/// How long each stage of solving a single input file took.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    /// `None` if the solver does not mark where parsing ends.
+    pub parse: Option<Duration>,
+    pub parts: BTreeMap<u32, Duration>,
+    pub total: Duration,
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(parse) = self.parse {
+            write!(f, "parse {parse:.2?}, ")?;
+        }
+        for (part, duration) in &self.parts {
+            write!(f, "part {part} {duration:.2?}, ")?;
+        }
+        write!(f, "total {:.2?}", self.total)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Statistics {
+    /// Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let seconds: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let variance =
+            seconds.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / seconds.len() as f64;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Timing statistics gathered over repeated runs of a solver on the same input.
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub runs: usize,
+    pub parse: Option<Statistics>,
+    pub parts: BTreeMap<u32, Statistics>,
+    pub total: Statistics,
+}
+
+impl BenchReport {
+    /// Returns `None` if there are no runs to summarize.
+    pub fn from_runs(runs: &[Timings]) -> Option<Self> {
+        let total =
+            Statistics::from_samples(&runs.iter().map(|run| run.total).collect::<Vec<_>>())?;
+        let parse = runs
+            .iter()
+            .map(|run| run.parse)
+            .collect::<Option<Vec<_>>>()
+            .and_then(|samples| Statistics::from_samples(&samples));
+        let mut part_samples: BTreeMap<u32, Vec<Duration>> = BTreeMap::new();
+        for run in runs {
+            for (&part, &duration) in &run.parts {
+                part_samples.entry(part).or_default().push(duration);
+            }
+        }
+        let parts = part_samples
+            .into_iter()
+            .filter_map(|(part, samples)| Some((part, Statistics::from_samples(&samples)?)))
+            .collect();
+        Some(Self {
+            runs: runs.len(),
+            parse,
+            parts,
+            total,
+        })
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, stage: &str, stats: &Statistics| {
+            writeln!(
+                f,
+                "{stage:<8} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+                stats.min, stats.median, stats.mean, stats.stddev
+            )
+        };
+
+        writeln!(
+            f,
+            "{:<8} {:>12} {:>12} {:>12} {:>12}",
+            format!("{} runs", self.runs),
+            "min",
+            "median",
+            "mean",
+            "stddev"
+        )?;
+        if let Some(parse) = &self.parse {
+            row(f, "parse", parse)?;
+        }
+        for (part, stats) in &self.parts {
+            row(f, &format!("part {part}"), stats)?;
+        }
+        row(f, "total", &self.total)
+    }
+}